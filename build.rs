@@ -0,0 +1,160 @@
+//! Generates the RGB -> palette lookup tables used by `palette.rs`.
+//!
+//! The tables are computed on the host with floating point so the target
+//! needs neither floats nor libm at runtime.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Spectra6 palette in panel order (White, Black, Yellow, Red, Green, Blue).
+const SPECTRA6: [[u8; 3]; 6] = [
+    [255, 255, 255],
+    [0, 0, 0],
+    [255, 255, 0],
+    [255, 0, 0],
+    [0, 255, 0],
+    [0, 0, 255],
+];
+
+/// All seven driver colors in native nibble order (Black, White, Yellow,
+/// Red, Orange, Blue, Green).
+const COLOR7: [[u8; 3]; 7] = [
+    [0, 0, 0],
+    [255, 255, 255],
+    [255, 255, 0],
+    [255, 0, 0],
+    [255, 128, 0],
+    [0, 0, 255],
+    [0, 255, 0],
+];
+
+/// Extra weight on OkLab chroma so near-grays stay neutral on this sparse palette.
+/// `palette::OKLAB_CHROMA_WEIGHT` must match.
+const CHROMA_WEIGHT: f64 = 3.0;
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn oklab(rgb: [u8; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    [
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    ]
+}
+
+fn nearest(rgb: [u8; 3], palette: &[[f64; 3]]) -> u8 {
+    let p = oklab(rgb);
+    let dist = |q: &[f64; 3]| {
+        let dl = p[0] - q[0];
+        let da = p[1] - q[1];
+        let db = p[2] - q[2];
+        dl * dl + CHROMA_WEIGHT * (da * da + db * db)
+    };
+    let mut best = 0;
+    for (i, q) in palette.iter().enumerate() {
+        if dist(q) < dist(&palette[best]) {
+            best = i;
+        }
+    }
+    best as u8
+}
+
+/// Index of the closest palette entry by the weighted RGB metric of
+/// `palette::dist2_weighted` (weights 3, 6, 1), first entry on ties.
+fn nearest_weighted(rgb: [u8; 3], palette: &[[u8; 3]]) -> u8 {
+    let dist = |q: &[u8; 3]| {
+        let d = |i: usize| rgb[i] as i32 - q[i] as i32;
+        3 * d(0) * d(0) + 6 * d(1) * d(1) + d(2) * d(2)
+    };
+    let mut best = 0;
+    for (i, q) in palette.iter().enumerate() {
+        if dist(q) < dist(&palette[best]) {
+            best = i;
+        }
+    }
+    best as u8
+}
+
+/// 32x32x32 table indexed by `(r >> 3) << 10 | (g >> 3) << 5 | (b >> 3)`,
+/// sampled at the center of each cell.
+fn table(mut nearest: impl FnMut([u8; 3]) -> u8) -> Vec<u8> {
+    let center = |q: usize| ((q << 3) + 4) as u8;
+    let mut table = Vec::with_capacity(32 * 32 * 32);
+    for r in 0..32 {
+        for g in 0..32 {
+            for b in 0..32 {
+                table.push(nearest([center(r), center(g), center(b)]));
+            }
+        }
+    }
+    table
+}
+
+fn lut(palette: &[[u8; 3]]) -> Vec<u8> {
+    let palette: Vec<[f64; 3]> = palette.iter().map(|&c| oklab(c)).collect();
+    table(|rgb| nearest(rgb, &palette))
+}
+
+/// Full scale of `palette::SRGB_TO_LINEAR`.
+const LINEAR_MAX: f64 = 4095.0;
+
+/// Rust source for the 12-bit sRGB decoding table of `palette.rs`.
+fn linear_table() -> String {
+    let linear: Vec<String> = (0..=255u8)
+        .map(|c| ((srgb_to_linear(c) * LINEAR_MAX).round() as u16).to_string())
+        .collect();
+    format!(
+        "/// sRGB 8-bit value -> linear light scaled to 0..=4095.\n\
+         pub(crate) const SRGB_TO_LINEAR: [u16; 256] = [{}];\n",
+        linear.join(", ")
+    )
+}
+
+/// Rust source for the `oklab` feature: the sRGB decoding table and the
+/// Spectra6 palette in OkLab, both as `f32`.
+fn oklab_tables() -> String {
+    let linear: Vec<String> = (0..=255u8)
+        .map(|c| format!("{:?}", srgb_to_linear(c) as f32))
+        .collect();
+    let palette: Vec<String> = SPECTRA6
+        .iter()
+        .map(|&c| format!("{:?}", oklab(c).map(|v| v as f32)))
+        .collect();
+    format!(
+        "/// sRGB 8-bit value -> linear light in 0.0..=1.0.\n\
+         static SRGB_TO_LINEAR_F32: [f32; 256] = [{}];\n\
+         /// `PALETTE` in OkLab (L, a, b).\n\
+         const PALETTE_OKLAB: [[f32; 3]; 6] = [{}];\n",
+        linear.join(", "),
+        palette.join(", ")
+    )
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_PAL_SPECTRA6").is_none() {
+        return;
+    }
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("spectra6_lut.bin"), lut(&SPECTRA6)).unwrap();
+    fs::write(Path::new(&out_dir).join("srgb_linear.rs"), linear_table()).unwrap();
+    if env::var_os("CARGO_FEATURE_COLOR_LUT").is_some() {
+        let color7 = table(|rgb| nearest_weighted(rgb, &COLOR7));
+        fs::write(Path::new(&out_dir).join("color7_lut.bin"), color7).unwrap();
+    }
+    if env::var_os("CARGO_FEATURE_OKLAB").is_some() {
+        fs::write(Path::new(&out_dir).join("oklab.rs"), oklab_tables()).unwrap();
+    }
+}
@@ -0,0 +1,78 @@
+//! Builds the driver into a `#![no_std]` static library that defines no
+//! global allocator. Linking fails if the crate pulls in `alloc` while its
+//! `alloc` feature is off.
+
+#![no_std]
+
+use core::convert::Infallible;
+use core::panic::PanicInfo;
+
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType as PinErrorType, InputPin, OutputPin};
+use embedded_hal::spi::{ErrorType as SpiErrorType, Operation, SpiDevice};
+use gdep073e01::dither::Bayer4x4;
+use gdep073e01::{Color, Gdep073e01, BUFFER_SIZE};
+
+struct Spi;
+struct Pin;
+struct Delay;
+
+impl SpiErrorType for Spi {
+    type Error = Infallible;
+}
+
+impl SpiDevice<u8> for Spi {
+    fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl PinErrorType for Pin {
+    type Error = Infallible;
+}
+
+impl OutputPin for Pin {
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+impl InputPin for Pin {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        Ok(true)
+    }
+}
+
+impl DelayNs for Delay {
+    fn delay_ns(&mut self, _: u32) {}
+}
+
+/// Draws and flushes a frame from a stack buffer.
+#[no_mangle]
+pub extern "C" fn gdep073e01_no_alloc_check() -> bool {
+    let mut buffer = [0u8; BUFFER_SIZE];
+    let mut display = Gdep073e01::new_with_buffer(Spi, Pin, Pin, Pin, Pin, Delay, &mut buffer);
+    let drawn = Rectangle::new(Point::new(10, 10), Size::new(50, 50))
+        .into_styled(PrimitiveStyle::with_fill(Color::Red))
+        .draw(&mut display)
+        .is_ok();
+    let mut target = display.into_dither_target(Bayer4x4);
+    let _ = target.clear(embedded_graphics::pixelcolor::Rgb888::new(200, 120, 40));
+    let mut display = target.into_inner();
+    drawn && display.init().is_ok() && display.flush().is_ok() && display.sleep().is_ok()
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    loop {}
+}
@@ -2,30 +2,95 @@
 
 use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::Rectangle};
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::dither::DitherStrategy;
+#[cfg(feature = "alloc")]
+use crate::palette::dist2_weighted;
 use crate::palette::map_rgb_to_spectra6_nearest;
 
+/// Weighted squared distance within which a calibration point applies,
+/// roughly 24 levels per channel.
+#[cfg(feature = "alloc")]
+const CALIBRATION_DIST2: u32 = 10 * 24 * 24;
+
 /// Wrap an embedded-graphics DrawTarget to apply palette+dither at draw time.
 pub struct DitherDrawTarget<T, S> {
     inner: T,
     strat: S,
+    #[cfg(feature = "alloc")]
+    calibration: Vec<([u8; 3], crate::Color)>,
 }
 
 impl<T, S> DitherDrawTarget<T, S> {
+    /// Wraps `inner`, mapping every drawn `Rgb888` color through `strat`.
     pub fn new(inner: T, strat: S) -> Self {
-        Self { inner, strat }
+        Self {
+            inner,
+            strat,
+            #[cfg(feature = "alloc")]
+            calibration: Vec::new(),
+        }
+    }
+    /// Overrides the mapping near measured colors, e.g. from a colorimeter.
+    ///
+    /// Each entry pairs a target RGB with the palette color that looks right
+    /// on the actual panel. A drawn color within about 24 levels per channel
+    /// of its nearest entry takes that entry's color directly, skipping the
+    /// strategy; anything further away is mapped by the strategy as usual.
+    /// An empty table removes the calibration.
+    #[cfg(feature = "alloc")]
+    pub fn set_calibration(&mut self, table: &[(Rgb888, crate::Color)]) {
+        self.calibration.clear();
+        self.calibration.extend(
+            table
+                .iter()
+                .map(|&(rgb, color)| ([rgb.r(), rgb.g(), rgb.b()], color)),
+        );
+    }
+    /// Palette color of the calibration point nearest to `rgb`, if close enough.
+    #[cfg(feature = "alloc")]
+    fn calibrated(&self, rgb: [u8; 3]) -> Option<crate::Color> {
+        self.calibration
+            .iter()
+            .map(|&(point, color)| (dist2_weighted(rgb, point), color))
+            .min_by_key(|&(d, _)| d)
+            .filter(|&(d, _)| d <= CALIBRATION_DIST2)
+            .map(|(_, color)| color)
+    }
+    /// Without `alloc` there is no calibration table.
+    #[cfg(not(feature = "alloc"))]
+    fn calibrated(&self, _rgb: [u8; 3]) -> Option<crate::Color> {
+        None
     }
+    /// Unwraps the display, dropping the strategy and any calibration.
     pub fn into_inner(self) -> T {
         self.inner
     }
+    /// Alias of [`display_mut`](Self::display_mut), which is the canonical name.
     pub fn inner_mut(&mut self) -> &mut T {
+        self.display_mut()
+    }
+    /// Mutable access to the wrapped display, e.g. to `flush()` after drawing.
+    pub fn display_mut(&mut self) -> &mut T {
         &mut self.inner
     }
+    /// Mutable access to the dither strategy, e.g. to reset its state between frames.
     pub fn strategy_mut(&mut self) -> &mut S {
         &mut self.strat
     }
 }
 
+impl<T, S: DitherStrategy> DitherDrawTarget<T, S> {
+    fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> crate::Color {
+        match self.calibrated(rgb) {
+            Some(color) => color,
+            None => self.strat.map(x, y, rgb).to_driver_color(),
+        }
+    }
+}
+
 impl<T, S, E> DrawTarget for DitherDrawTarget<T, S>
 where
     T: DrawTarget<Color = crate::Color, Error = E> + OriginDimensions,
@@ -38,12 +103,15 @@ where
     where
         I: IntoIterator<Item = embedded_graphics::Pixel<Self::Color>>,
     {
+        let bounds = self.inner.bounding_box();
         for Pixel(coord, rgb) in pixels.into_iter() {
+            // Off-screen pixels would wrap to huge coordinates or index past
+            // a strategy's error rows; the display clips them anyway.
+            if !bounds.contains(coord) {
+                continue;
+            }
             let (x, y) = (coord.x as u32, coord.y as u32);
-            let c6 = self
-                .strat
-                .map(x, y, [rgb.r(), rgb.g(), rgb.b()])
-                .to_driver_color();
+            let c6 = self.map(x, y, [rgb.r(), rgb.g(), rgb.b()]);
             // Forward as single pixel
             self.inner.draw_iter(core::iter::once(Pixel(coord, c6)))?;
         }
@@ -51,8 +119,9 @@ where
     }
 
     fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-        // Use a simple iterator over the area mapping each pixel.
-        let rb = area.bounding_box();
+        // Map each on-screen pixel; clipping first keeps oversized areas
+        // from walking, and feeding the strategy, far past the display.
+        let rb = area.intersection(&self.inner.bounding_box());
         let tl = rb.top_left;
         let w = rb.size.width as i32;
         let h = rb.size.height as i32;
@@ -60,10 +129,7 @@ where
             for x in tl.x..(tl.x + w) {
                 let sx = x as u32;
                 let sy = y as u32;
-                let c6 = self
-                    .strat
-                    .map(sx, sy, [color.r(), color.g(), color.b()])
-                    .to_driver_color();
+                let c6 = self.map(sx, sy, [color.r(), color.g(), color.b()]);
                 self.inner
                     .draw_iter(core::iter::once(Pixel(Point::new(x, y), c6)))?;
             }
@@ -72,7 +138,10 @@ where
     }
 
     fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-        let c6 = map_rgb_to_spectra6_nearest([color.r(), color.g(), color.b()]).to_driver_color();
+        let rgb = [color.r(), color.g(), color.b()];
+        let c6 = self
+            .calibrated(rgb)
+            .unwrap_or_else(|| map_rgb_to_spectra6_nearest(rgb).to_driver_color());
         self.inner.clear(c6)
     }
 }
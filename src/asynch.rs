@@ -0,0 +1,435 @@
+//! Async driver on `embedded-hal-async`, for executors such as Embassy.
+//!
+//! [`Gdep073e01Async`] covers the basic lifecycle (init, flush, sleep) and
+//! embedded-graphics drawing. BUSY is awaited through the
+//! [`Wait`](embedded_hal_async::digital::Wait) trait instead of polled, so
+//! the executor can run other tasks during the seconds-long refresh. There
+//! is no built-in timeout; race the future against your executor's timer.
+//!
+//! With the `test-util` feature, [`test_util`] provides recording mocks and
+//! a minimal executor for unit-testing async drawing code on the host.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::{delay::DelayNs, digital::Wait, spi::SpiDevice};
+
+use crate::{
+    init_sequence, pack_nibble, BoardPreset, BoosterProfile, Color, Error, FrameRate, BUFFER_SIZE,
+    CDI_DATA, CMDH_DATA, CMD_DATA_START_TRANSMISSION, CMD_DEEP_SLEEP, CMD_DISPLAY_REFRESH,
+    CMD_POWER_OFF, CMD_POWER_ON, HEIGHT, PANEL_SETTING_DATA, REFRESH_FULL, WIDTH,
+};
+
+/// Async GDEP073E01 driver with a heap-allocated frame buffer.
+///
+/// Uses the `BoardPreset::Generic` timings and chunk size, the default
+/// booster profile and the reference panel settings. Pins other than BUSY
+/// are driven synchronously, as setting a GPIO never blocks.
+pub struct Gdep073e01Async<SPI, CS, DC, RST, BUSY, DELAY> {
+    spi: SPI,
+    cs: CS,
+    dc: DC,
+    rst: RST,
+    busy: BUSY,
+    delay: DELAY,
+    buffer: Box<[u8]>,
+    busy_active_high: bool,
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>
+    Gdep073e01Async<SPI, CS, DC, RST, BUSY, DELAY>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE> + Wait,
+    DELAY: DelayNs,
+{
+    /// Creates a driver with a white buffer; see [`Gdep073e01::new`](crate::Gdep073e01::new).
+    pub fn new(spi: SPI, cs: CS, dc: DC, rst: RST, busy: BUSY, delay: DELAY) -> Self {
+        Self {
+            spi,
+            cs,
+            dc,
+            rst,
+            busy,
+            delay,
+            buffer: vec![0x11; BUFFER_SIZE].into_boxed_slice(),
+            busy_active_high: BoardPreset::Generic.busy_active_high(),
+        }
+    }
+
+    /// Resets and initializes the display.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures.
+    pub async fn init(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let reset_ms = BoardPreset::Generic.reset_delay_ms();
+        self.rst.set_low().map_err(Error::Pin)?;
+        self.delay.delay_ms(reset_ms).await;
+        self.rst.set_high().map_err(Error::Pin)?;
+        self.delay.delay_ms(reset_ms).await;
+
+        // The sequence is built with a sync callback; replay it here
+        let mut commands: Vec<(u8, Vec<u8>)> = Vec::new();
+        init_sequence::<()>(
+            CMDH_DATA,
+            BoosterProfile::default(),
+            PANEL_SETTING_DATA,
+            CDI_DATA,
+            FrameRate::default(),
+            |command, data| {
+                commands.push((command, data.to_vec()));
+                Ok(())
+            },
+        )
+        .ok();
+        for (command, data) in &commands {
+            self.command_with_data(*command, data).await?;
+        }
+        self.write(false, &[CMD_POWER_ON]).await?;
+        self.wait_until_idle().await
+    }
+
+    /// Sends the buffer and refreshes, awaiting the end of the refresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures.
+    pub async fn flush(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write(false, &[CMD_DATA_START_TRANSMISSION]).await?;
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let mut result = Ok(());
+        for chunk in self.buffer.chunks(BoardPreset::Generic.chunk_size()) {
+            result = self.spi.write(chunk).await.map_err(Error::Spi);
+            if result.is_err() {
+                break;
+            }
+        }
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.command_with_data(CMD_DISPLAY_REFRESH, &[REFRESH_FULL])
+            .await?;
+        self.wait_until_idle().await
+    }
+
+    /// Powers off and puts the display into deep sleep; wake it with `init()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures.
+    pub async fn sleep(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.command_with_data(CMD_POWER_OFF, &[0x00]).await?;
+        self.wait_until_idle().await?;
+        self.command_with_data(CMD_DEEP_SLEEP, &[0xA5]).await
+    }
+
+    async fn command_with_data(
+        &mut self,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write(false, &[command]).await?;
+        self.write(true, data).await
+    }
+
+    async fn write(&mut self, is_data: bool, bytes: &[u8]) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if is_data {
+            self.dc.set_high().map_err(Error::Pin)?;
+        } else {
+            self.dc.set_low().map_err(Error::Pin)?;
+        }
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = self.spi.write(bytes).await.map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result
+    }
+
+    async fn wait_until_idle(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if self.busy_active_high {
+            self.busy.wait_for_low().await
+        } else {
+            self.busy.wait_for_high().await
+        }
+        .map_err(Error::Busy)
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> Gdep073e01Async<SPI, CS, DC, RST, BUSY, DELAY> {
+    /// Sets a pixel in the buffer; out-of-range pixels are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if x >= WIDTH || y >= HEIGHT {
+            return;
+        }
+        let byte = &mut self.buffer[((y * WIDTH + x) / 2) as usize];
+        *byte = pack_nibble(*byte, x, color);
+    }
+
+    /// Clears the buffer with `color`.
+    pub fn clear_buffer(&mut self, color: Color) {
+        self.buffer.fill((color as u8) * 0x11);
+    }
+
+    /// Releases the peripherals.
+    pub fn release(self) -> (SPI, CS, DC, RST, BUSY, DELAY) {
+        (self.spi, self.cs, self.dc, self.rst, self.busy, self.delay)
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> DrawTarget for Gdep073e01Async<SPI, CS, DC, RST, BUSY, DELAY> {
+    type Color = Color;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Ok((x, y)) = <(u32, u32)>::try_from(point) {
+                self.set_pixel(x, y, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for point in area.intersection(&self.bounding_box()).points() {
+            self.set_pixel(point.x as u32, point.y as u32, color);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_buffer(color);
+        Ok(())
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> OriginDimensions
+    for Gdep073e01Async<SPI, CS, DC, RST, BUSY, DELAY>
+{
+    fn size(&self) -> Size {
+        Size::new(WIDTH, HEIGHT)
+    }
+}
+
+/// Recording mocks and a deterministic executor for testing async code.
+///
+/// The mocks never fail. [`ScriptedBusy`] reports the panel busy for a
+/// set number of polls of its wait future, and [`block_on`] polls a future
+/// to completion on the current thread, so a test that awaits a flush
+/// always takes the same path.
+#[cfg(feature = "test-util")]
+pub mod test_util {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+
+    use embedded_hal::digital::{ErrorType as DigitalErrorType, InputPin, OutputPin};
+    use embedded_hal_async::{
+        delay::DelayNs,
+        digital::Wait,
+        spi::{ErrorType as SpiErrorType, Operation, SpiDevice},
+    };
+
+    use super::Gdep073e01Async;
+
+    /// SPI device recording each write.
+    #[derive(Debug, Default)]
+    pub struct RecordingSpi {
+        /// Written buffers, one entry per write operation.
+        pub writes: Vec<Vec<u8>>,
+    }
+
+    impl RecordingSpi {
+        /// Total number of bytes written.
+        pub fn bytes_written(&self) -> usize {
+            self.writes.iter().map(Vec::len).sum()
+        }
+    }
+
+    impl SpiErrorType for RecordingSpi {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice<u8> for RecordingSpi {
+        async fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Infallible> {
+            for op in operations {
+                if let Operation::Write(data) = op {
+                    self.writes.push(data.to_vec());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Output pin recording each level set, `true` for high.
+    #[derive(Debug, Default)]
+    pub struct RecordingPin {
+        /// Levels in the order they were set.
+        pub states: Vec<bool>,
+    }
+
+    impl DigitalErrorType for RecordingPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for RecordingPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.states.push(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.states.push(true);
+            Ok(())
+        }
+    }
+
+    /// Active-high BUSY input whose waits stay pending for a scripted number of polls.
+    #[derive(Debug, Default)]
+    pub struct ScriptedBusy {
+        /// Polls each wait future returns `Pending` before completing.
+        pub busy_polls: usize,
+        /// Waits completed so far.
+        pub waits: usize,
+    }
+
+    impl DigitalErrorType for ScriptedBusy {
+        type Error = Infallible;
+    }
+
+    impl InputPin for ScriptedBusy {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(false)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            Ok(true)
+        }
+    }
+
+    impl ScriptedBusy {
+        async fn wait(&mut self) -> Result<(), Infallible> {
+            let mut remaining = self.busy_polls;
+            core::future::poll_fn(|cx| {
+                if remaining == 0 {
+                    return Poll::Ready(());
+                }
+                remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            })
+            .await;
+            self.waits += 1;
+            Ok(())
+        }
+    }
+
+    impl Wait for ScriptedBusy {
+        async fn wait_for_high(&mut self) -> Result<(), Infallible> {
+            self.wait().await
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Infallible> {
+            self.wait().await
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Infallible> {
+            self.wait().await
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Infallible> {
+            self.wait().await
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Infallible> {
+            self.wait().await
+        }
+    }
+
+    /// Delay completing at once, recording the requested durations.
+    #[derive(Debug, Default)]
+    pub struct RecordingDelay {
+        /// Requested delays in nanoseconds.
+        pub delays_ns: Vec<u32>,
+    }
+
+    impl DelayNs for RecordingDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.delays_ns.push(ns);
+        }
+    }
+
+    /// Async driver wired to the recording mocks.
+    pub type TestDisplayAsync = Gdep073e01Async<
+        RecordingSpi,
+        RecordingPin,
+        RecordingPin,
+        RecordingPin,
+        ScriptedBusy,
+        RecordingDelay,
+    >;
+
+    /// Creates an async driver on fresh recording mocks.
+    pub fn test_display_async() -> TestDisplayAsync {
+        Gdep073e01Async::new(
+            RecordingSpi::default(),
+            RecordingPin::default(),
+            RecordingPin::default(),
+            RecordingPin::default(),
+            ScriptedBusy::default(),
+            RecordingDelay::default(),
+        )
+    }
+
+    /// Polls `future` to completion on the current thread.
+    ///
+    /// Busy-polls with a no-op waker, which is enough for the mocks here:
+    /// their futures make progress on every poll.
+    pub fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::test_util::*;
+    use crate::{Color, BUFFER_SIZE, CMD_DATA_START_TRANSMISSION, CMD_DISPLAY_REFRESH};
+
+    #[test]
+    fn flush_sends_frame_and_awaits_busy() {
+        let mut display = test_display_async();
+        display.busy.busy_polls = 3;
+        block_on(display.init()).unwrap();
+        assert_eq!(display.rst.states, [false, true]);
+        assert_eq!(display.busy.waits, 1);
+
+        display.spi.writes.clear();
+        display.set_pixel(0, 0, Color::Red);
+        block_on(display.flush()).unwrap();
+        assert_eq!(display.spi.writes[0], [CMD_DATA_START_TRANSMISSION]);
+        assert_eq!(display.spi.writes[1][0], 0x31);
+        assert_eq!(display.spi.bytes_written(), 1 + BUFFER_SIZE + 2);
+        assert_eq!(
+            display.spi.writes[display.spi.writes.len() - 2],
+            [CMD_DISPLAY_REFRESH]
+        );
+        assert_eq!(display.busy.waits, 2);
+    }
+}
@@ -0,0 +1,91 @@
+//! Declarative construction of the driver.
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+    spi::SpiDevice,
+};
+
+use crate::{cdi_for_border, Color, Gdep073e01, Rotation, BUSY_TIMEOUT_MS};
+
+/// Collects the peripherals and settings for a [`Gdep073e01`].
+///
+/// `build()` only assembles the driver; it does not touch the hardware.
+/// Call `init()` on the result to reset and configure the panel, which also
+/// sends the chosen border color.
+///
+/// ```ignore
+/// let mut display = Gdep073e01Builder::new(spi, cs, dc, rst, busy, delay)
+///     .rotation(Rotation::Deg90)
+///     .busy_timeout_ms(60_000)
+///     .border_color(Color::Black)
+///     .build();
+/// display.init()?;
+/// ```
+pub struct Gdep073e01Builder<SPI, CS, DC, RST, BUSY, DELAY> {
+    spi: SPI,
+    cs: CS,
+    dc: DC,
+    rst: RST,
+    busy: BUSY,
+    delay: DELAY,
+    rotation: Rotation,
+    busy_timeout_ms: u32,
+    border_color: Color,
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> Gdep073e01Builder<SPI, CS, DC, RST, BUSY, DELAY> {
+    /// Starts a builder with the driver defaults; see [`Gdep073e01::new`] for the arguments.
+    pub fn new(spi: SPI, cs: CS, dc: DC, rst: RST, busy: BUSY, delay: DELAY) -> Self {
+        Self {
+            spi,
+            cs,
+            dc,
+            rst,
+            busy,
+            delay,
+            rotation: Rotation::Deg0,
+            busy_timeout_ms: BUSY_TIMEOUT_MS,
+            border_color: Color::White,
+        }
+    }
+
+    /// Drawing rotation, as for [`Gdep073e01::set_rotation`].
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// BUSY wait limit, as for [`Gdep073e01::set_busy_timeout_ms`].
+    pub fn busy_timeout_ms(mut self, ms: u32) -> Self {
+        self.busy_timeout_ms = ms;
+        self
+    }
+
+    /// Border color, as for [`Gdep073e01::set_border_color`]; sent by `init()`.
+    pub fn border_color(mut self, color: Color) -> Self {
+        self.border_color = color;
+        self
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>
+    Gdep073e01Builder<SPI, CS, DC, RST, BUSY, DELAY>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    /// Assembles the driver without communicating with the panel.
+    pub fn build(self) -> Gdep073e01<'static, SPI, CS, DC, RST, BUSY, DELAY> {
+        let mut display =
+            Gdep073e01::new(self.spi, self.cs, self.dc, self.rst, self.busy, self.delay);
+        display.set_rotation(self.rotation);
+        display.set_busy_timeout_ms(self.busy_timeout_ms);
+        display.cdi = cdi_for_border(self.border_color);
+        display
+    }
+}
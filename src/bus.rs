@@ -0,0 +1,160 @@
+//! `SpiBus` support for HALs without an `SpiDevice` implementation.
+
+use embedded_hal::spi::{ErrorType, Operation, SpiBus, SpiDevice};
+
+/// Exposes an exclusively owned `SpiBus` as an `SpiDevice` without its own CS.
+///
+/// The driver already frames every transfer with its CS pin, so this wrapper
+/// only forwards the operations and flushes the bus before returning, making
+/// sure the last byte is clocked out before CS is released. Build a driver
+/// around it with [`Gdep073e01::new_with_bus`](crate::Gdep073e01::new_with_bus).
+///
+/// `Operation::DelayNs` is not supported and is skipped; the driver never
+/// issues it.
+#[derive(Debug)]
+pub struct ManualCsBus<BUS> {
+    bus: BUS,
+}
+
+impl<BUS> ManualCsBus<BUS> {
+    /// Wraps `bus`.
+    pub fn new(bus: BUS) -> Self {
+        Self { bus }
+    }
+
+    /// Returns the wrapped bus.
+    pub fn into_inner(self) -> BUS {
+        self.bus
+    }
+}
+
+impl<BUS: ErrorType> ErrorType for ManualCsBus<BUS> {
+    type Error = BUS::Error;
+}
+
+impl<BUS: SpiBus<u8>> SpiDevice<u8> for ManualCsBus<BUS> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for op in operations {
+            match op {
+                Operation::Read(buf) => self.bus.read(buf)?,
+                Operation::Write(buf) => self.bus.write(buf)?,
+                Operation::Transfer(read, write) => self.bus.transfer(read, write)?,
+                Operation::TransferInPlace(buf) => self.bus.transfer_in_place(buf)?,
+                Operation::DelayNs(_) => {}
+            }
+        }
+        self.bus.flush()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::Gdep073e01;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::RefCell;
+    use core::convert::Infallible;
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal::digital::{InputPin, OutputPin};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        CsLow,
+        CsHigh,
+        Write(Vec<u8>),
+        Flush,
+    }
+
+    type Log = Rc<RefCell<Vec<Event>>>;
+
+    struct MockBus(Log);
+
+    impl ErrorType for MockBus {
+        type Error = Infallible;
+    }
+
+    impl SpiBus<u8> for MockBus {
+        fn read(&mut self, _: &mut [u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn write(&mut self, words: &[u8]) -> Result<(), Infallible> {
+            self.0.borrow_mut().push(Event::Write(words.to_vec()));
+            Ok(())
+        }
+        fn transfer(&mut self, _: &mut [u8], _: &[u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn transfer_in_place(&mut self, _: &mut [u8]) -> Result<(), Infallible> {
+            Ok(())
+        }
+        fn flush(&mut self) -> Result<(), Infallible> {
+            self.0.borrow_mut().push(Event::Flush);
+            Ok(())
+        }
+    }
+
+    /// Output pin that logs its edges when it is the CS line.
+    struct MockPin(Option<Log>);
+
+    impl embedded_hal::digital::ErrorType for MockPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            if let Some(log) = &self.0 {
+                log.borrow_mut().push(Event::CsLow);
+            }
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            if let Some(log) = &self.0 {
+                log.borrow_mut().push(Event::CsHigh);
+            }
+            Ok(())
+        }
+    }
+
+    impl InputPin for MockPin {
+        fn is_high(&mut self) -> Result<bool, Infallible> {
+            Ok(false)
+        }
+        fn is_low(&mut self) -> Result<bool, Infallible> {
+            Ok(true)
+        }
+    }
+
+    struct MockDelay;
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, _: u32) {}
+    }
+
+    #[test]
+    fn cs_frames_each_transfer() {
+        let log = Log::default();
+        let mut display = Gdep073e01::new_with_bus(
+            MockBus(log.clone()),
+            MockPin(Some(log.clone())),
+            MockPin(None),
+            MockPin(None),
+            MockPin(None),
+            MockDelay,
+        );
+        display.sleep().unwrap();
+
+        let log = log.borrow();
+        assert!(!log.is_empty());
+        for frame in log.chunks(4) {
+            assert!(
+                matches!(
+                    frame,
+                    [Event::CsLow, Event::Write(_), Event::Flush, Event::CsHigh]
+                ),
+                "{frame:?}"
+            );
+        }
+        assert!(log.contains(&Event::Write(alloc::vec![0x07])));
+    }
+}
@@ -0,0 +1,86 @@
+//! Compact 3-bit-per-pixel frame storage.
+//!
+//! The seven colors fit in 3 bits, so a full 800x480 frame packs into
+//! 144,000 bytes instead of the driver's 192,000. Pixels are stored as a
+//! little-endian bit stream: pixel `i` occupies bits `3 * i .. 3 * i + 3`,
+//! least significant bit first, so eight pixels fill three bytes. Frames
+//! packed here load straight into the driver with
+//! [`Gdep073e01::load_3bpp`](crate::Gdep073e01::load_3bpp).
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::Color;
+
+/// Number of bytes `pixels` pixels take at 3 bits per pixel.
+pub const fn packed_3bpp_len(pixels: usize) -> usize {
+    (pixels * 3).div_ceil(8)
+}
+
+/// Packs colors at 3 bits per pixel; trailing bits of the last byte are zero.
+#[cfg(feature = "alloc")]
+pub fn pack_3bpp(pixels: &[Color]) -> Vec<u8> {
+    let mut out = vec![0u8; packed_3bpp_len(pixels.len())];
+    for (i, &color) in pixels.iter().enumerate() {
+        let bit = i * 3;
+        let bits = u16::from(color.to_bits3()) << (bit % 8);
+        out[bit / 8] |= bits as u8;
+        if let Some(next) = out.get_mut(bit / 8 + 1) {
+            *next |= (bits >> 8) as u8;
+        }
+    }
+    out
+}
+
+/// Unpacks `pixels` colors packed by [`pack_3bpp`].
+///
+/// Returns `None` if `data` is too short or holds the unused code 7.
+#[cfg(feature = "alloc")]
+pub fn unpack_3bpp(data: &[u8], pixels: usize) -> Option<Vec<Color>> {
+    if data.len() < packed_3bpp_len(pixels) {
+        return None;
+    }
+    codes_3bpp(data)
+        .take(pixels)
+        .map(Color::from_bits3)
+        .collect()
+}
+
+/// Raw 3-bit codes of `data`, in pixel order.
+pub(crate) fn codes_3bpp(data: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    (0..data.len() * 8 / 3).map(move |i| {
+        let bit = i * 3;
+        let lo = u16::from(data[bit / 8]);
+        let hi = u16::from(data.get(bit / 8 + 1).copied().unwrap_or(0));
+        (((hi << 8) | lo) >> (bit % 8)) as u8 & 0x07
+    })
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let pixels: Vec<Color> = (0..37).map(|i| Color::ALL[i * 5 % Color::COUNT]).collect();
+        let packed = pack_3bpp(&pixels);
+        assert_eq!(packed.len(), 14);
+        assert_eq!(unpack_3bpp(&packed, pixels.len()), Some(pixels));
+
+        // Eight pixels fill three bytes exactly
+        let packed = pack_3bpp(&[Color::Green; 8]);
+        assert_eq!(packed, [0xB6, 0x6D, 0xDB]);
+        assert_eq!(packed_3bpp_len(800 * 480), 144_000);
+    }
+
+    #[test]
+    fn unpack_rejects_bad_input() {
+        let packed = pack_3bpp(&[Color::Red; 3]);
+        assert_eq!(unpack_3bpp(&packed, 6), None);
+        // Code 7 is not a color
+        assert_eq!(unpack_3bpp(&[0x07], 1), None);
+    }
+}
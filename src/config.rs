@@ -0,0 +1,86 @@
+//! Board and panel configuration presets.
+
+/// BUSY polarity, reset timing and SPI chunk size for a board.
+///
+/// Applied with [`Gdep073e01::with_defaults_for`](crate::Gdep073e01::with_defaults_for).
+/// Presets for specific adapter boards are only added together with a
+/// vendor source for each of their values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum BoardPreset {
+    /// Driver defaults: BUSY active high, 10 ms reset pulse, 4096-byte SPI chunks.
+    #[default]
+    Generic,
+}
+
+impl BoardPreset {
+    /// Whether the BUSY line reads high while the panel is busy.
+    pub const fn busy_active_high(self) -> bool {
+        match self {
+            BoardPreset::Generic => true,
+        }
+    }
+
+    /// Reset pulse low/high hold time in milliseconds.
+    pub const fn reset_delay_ms(self) -> u32 {
+        match self {
+            BoardPreset::Generic => 10,
+        }
+    }
+
+    /// Number of bytes sent per SPI write when transmitting the frame buffer.
+    pub const fn chunk_size(self) -> usize {
+        match self {
+            BoardPreset::Generic => 4096,
+        }
+    }
+}
+
+/// Booster soft-start settings sent during initialization.
+///
+/// The soft-start phases ramp the charge pumps up before each refresh.
+/// Profiles other than the vendor reference are only added together with a
+/// vendor source for their bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum BoosterProfile {
+    /// Vendor reference values (default).
+    #[default]
+    Quality,
+}
+
+/// Frame rate of the panel's waveform clock (PLL control, command 0x30).
+///
+/// The waveform is a fixed number of frames, so refresh time scales
+/// inversely with the frame rate. Only the vendor reference rate is
+/// provided; other rates are only added together with the datasheet's PLL
+/// byte for each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum FrameRate {
+    /// 50 Hz: vendor reference (default).
+    #[default]
+    Hz50,
+}
+
+impl FrameRate {
+    /// Data byte for `CMD_PLL_CONTROL` (command 0x30).
+    pub const fn pll_byte(self) -> u8 {
+        match self {
+            FrameRate::Hz50 => 0x08,
+        }
+    }
+}
+
+impl BoosterProfile {
+    /// Data bytes for `BTST1`, `BTST2` and `BTST3` (commands 0x05, 0x06, 0x08).
+    pub const fn booster_bytes(self) -> [[u8; 4]; 3] {
+        match self {
+            BoosterProfile::Quality => [
+                [0x40, 0x1F, 0x1F, 0x2C],
+                [0x6F, 0x1F, 0x17, 0x49],
+                [0x6F, 0x1F, 0x1F, 0x22],
+            ],
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Debugging helpers for inspecting packed frame buffers.
+//! Requires the `std` feature.
+
+use std::vec::Vec;
+
+use crate::{Color, WIDTH};
+
+/// Lists every pixel that differs between two packed frame buffers.
+///
+/// Buffers use the driver layout (row-major, two pixels per byte, high nibble
+/// first). Each entry is `(x, y, color_in_a, color_in_b)`; a nibble outside the
+/// palette (`0x7..=0xF`) decodes as `None`. Only the common prefix of the two
+/// buffers is compared.
+pub fn diff_buffers(a: &[u8], b: &[u8]) -> Vec<(u32, u32, Option<Color>, Option<Color>)> {
+    let mut diffs = Vec::new();
+    for (i, (&byte_a, &byte_b)) in a.iter().zip(b).enumerate() {
+        if byte_a == byte_b {
+            continue;
+        }
+        let pixel = i as u32 * 2;
+        for (offset, shift) in [(0, 4), (1, 0)] {
+            let nibble_a = (byte_a >> shift) & 0x0F;
+            let nibble_b = (byte_b >> shift) & 0x0F;
+            if nibble_a != nibble_b {
+                let p = pixel + offset;
+                diffs.push((
+                    p % WIDTH,
+                    p / WIDTH,
+                    Color::from_nibble(nibble_a),
+                    Color::from_nibble(nibble_b),
+                ));
+            }
+        }
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    #[test]
+    fn single_pixel_difference() {
+        let a = vec![0x11; 1000];
+        let mut b = a.clone();
+        // Pixel (803, 0) wraps to x = 3, y = 1
+        b[401] = 0x13;
+
+        assert_eq!(
+            diff_buffers(&a, &b),
+            vec![(3, 1, Some(Color::White), Some(Color::Red))]
+        );
+        assert!(diff_buffers(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn invalid_nibble_is_not_white() {
+        let a = vec![0x11; 4];
+        let mut b = a.clone();
+        b[0] = 0x71;
+        b[1] = 0x1F;
+
+        assert_eq!(
+            diff_buffers(&a, &b),
+            vec![
+                (0, 0, Some(Color::White), None),
+                (3, 0, Some(Color::White), None),
+            ]
+        );
+    }
+}
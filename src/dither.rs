@@ -1,26 +1,257 @@
 //! Dithering and halftone strategies to map RGB->Spectra6.
 //! Feature-gated implementations, no_std by default; FS requires alloc.
 
-use crate::palette::{add_bias, map_rgb_to_spectra6_nearest, Spectra6};
+#[cfg(feature = "dither-bayer")]
+use crate::palette::add_bias;
+use crate::palette::{dist2_weighted, map_rgb_to_spectra6_nearest, Spectra6, PALETTE};
 
 /// Strategy trait for per-pixel mapping with spatial/temporal context.
 pub trait DitherStrategy {
     /// Map an sRGB triple at pixel (x,y) to Spectra6.
     /// `x,y` are absolute framebuffer coords for matrix patterns.
     fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6;
+
+    /// Discard any state carried between pixels, e.g. before a new frame.
+    /// Stateless strategies keep the default no-op.
+    fn reset(&mut self) {}
+}
+
+/// Marker for strategies whose output depends only on `(x, y, rgb)`.
+///
+/// Such strategies carry no error or scanline state, so clones can process
+/// disjoint regions in any order (or in parallel) and still produce the same
+/// result as a single serial pass. Error-diffusion strategies like
+/// Floyd–Steinberg must not implement it.
+pub trait StatelessDither: DitherStrategy + Clone + Send + Sync {}
+
+/// Plain nearest-color mapping without dithering.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Nearest;
+
+impl DitherStrategy for Nearest {
+    fn map(&mut self, _x: u32, _y: u32, rgb: [u8; 3]) -> Spectra6 {
+        map_rgb_to_spectra6_nearest(rgb)
+    }
+}
+
+impl StatelessDither for Nearest {}
+
+/// Renders orange-ish inputs as a spatial mix of Red and Yellow.
+///
+/// The panel's native Orange settles more slowly than the other pigments;
+/// this strategy avoids it by mixing Red and Yellow on a 2x2 ordered pattern,
+/// with the share of Yellow following the green channel. Inputs closer to
+/// orange (`[255, 128, 0]`) than to any Spectra6 color are mixed; everything
+/// else maps to the nearest color.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrangeAsRedYellow;
+
+impl OrangeAsRedYellow {
+    const ORANGE: [u8; 3] = [255, 128, 0];
+    /// 2x2 Bayer thresholds scaled to 0..=255
+    const THRESHOLDS: [[u8; 2]; 2] = [[32, 160], [224, 96]];
+}
+
+impl DitherStrategy for OrangeAsRedYellow {
+    fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6 {
+        let nearest = map_rgb_to_spectra6_nearest(rgb);
+        if dist2_weighted(rgb, Self::ORANGE) >= dist2_weighted(rgb, PALETTE[nearest as usize]) {
+            return nearest;
+        }
+        if rgb[1] > Self::THRESHOLDS[(y & 1) as usize][(x & 1) as usize] {
+            Spectra6::Yellow
+        } else {
+            Spectra6::Red
+        }
+    }
+}
+
+impl StatelessDither for OrangeAsRedYellow {}
+
+/// Dithering approach picked by [`choose_strategy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategyKind {
+    /// Flat artwork already close to the palette: [`Nearest`].
+    Nearest,
+    /// Smooth gradients: ordered `Bayer4x4`.
+    Bayer,
+    /// Noisy, photo-like content: `FloydSteinberg`.
+    FloydSteinberg,
+}
+
+/// Picks a dithering approach for a row-major image from simple statistics.
+///
+/// Up to about 1024 pixels are sampled at an even stride. If at least 90%
+/// of them lie within roughly 24 levels per channel of a palette color the
+/// image is treated as flat UI and gets `Nearest`. Otherwise the luminance
+/// steps to the right and lower neighbors of each sample are measured: when
+/// at least 90% are at most 12 levels the content is a smooth gradient and
+/// gets `Bayer`, anything busier gets `FloydSteinberg`. An empty image or a
+/// `src` shorter than `width * height` yields `Nearest`.
+pub fn choose_strategy(width: u32, height: u32, src: &[[u8; 3]]) -> StrategyKind {
+    const MAX_SAMPLES: usize = 1024;
+    const FLAT_DIST2: u32 = 10 * 24 * 24;
+    const SMOOTH_STEP: u16 = 12;
+
+    let (w, h) = (width as usize, height as usize);
+    let len = w * h;
+    if len == 0 || src.len() < len {
+        return StrategyKind::Nearest;
+    }
+    let luma = |rgb: [u8; 3]| (3 * rgb[0] as u16 + 6 * rgb[1] as u16 + rgb[2] as u16) / 10;
+
+    let (mut samples, mut flat, mut steps, mut smooth) = (0usize, 0usize, 0usize, 0usize);
+    for i in (0..len).step_by((len / MAX_SAMPLES).max(1)) {
+        let rgb = src[i];
+        samples += 1;
+        let nearest = PALETTE[map_rgb_to_spectra6_nearest(rgb) as usize];
+        if dist2_weighted(rgb, nearest) <= FLAT_DIST2 {
+            flat += 1;
+        }
+        let right = (i % w + 1 < w).then(|| src[i + 1]);
+        let below = (i + w < len).then(|| src[i + w]);
+        for neighbor in [right, below].into_iter().flatten() {
+            steps += 1;
+            if luma(rgb).abs_diff(luma(neighbor)) <= SMOOTH_STEP {
+                smooth += 1;
+            }
+        }
+    }
+
+    if flat * 10 >= samples * 9 {
+        StrategyKind::Nearest
+    } else if smooth * 10 >= steps * 9 {
+        StrategyKind::Bayer
+    } else {
+        StrategyKind::FloydSteinberg
+    }
 }
 
+/// Pulls RGB rows on demand and dithers them in scanline order.
+///
+/// Rows come from `source(y, row)`, which fills `row` (`width` pixels) and
+/// returns `false` once the image has ended. The streamer keeps a two-row
+/// window, the row being mapped plus one lookahead row, so the source can
+/// decode or read lazily (e.g. from an SD card) while stateful strategies
+/// such as Floyd–Steinberg still see every pixel exactly once, left to
+/// right and top to bottom. The output then matches dithering the whole
+/// image from a buffer.
+#[cfg(feature = "alloc")]
+pub struct RowStreamer<S, F> {
+    strat: S,
+    source: F,
+    rows: [alloc::vec::Vec<[u8; 3]>; 2],
+    /// Rows held in `rows`, 0..=2
+    buffered: usize,
+    y: u32,
+    ended: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<S, F> RowStreamer<S, F>
+where
+    S: DitherStrategy,
+    F: FnMut(u32, &mut [[u8; 3]]) -> bool,
+{
+    /// Creates a streamer for rows of `width` pixels, resetting `strat`.
+    pub fn new(mut strat: S, width: u32, source: F) -> Self {
+        strat.reset();
+        let row = alloc::vec![[0; 3]; width as usize];
+        Self {
+            strat,
+            source,
+            rows: [row.clone(), row],
+            buffered: 0,
+            y: 0,
+            ended: false,
+        }
+    }
+
+    /// Dithers the next row into `out`, returning its y, or `None` at the end.
+    ///
+    /// `out` receives at most `width` pixels.
+    pub fn next_row(&mut self, out: &mut [Spectra6]) -> Option<u32> {
+        while self.buffered < 2 && !self.ended {
+            let y = self.y + self.buffered as u32;
+            if (self.source)(y, &mut self.rows[self.buffered]) {
+                self.buffered += 1;
+            } else {
+                self.ended = true;
+            }
+        }
+        if self.buffered == 0 {
+            return None;
+        }
+
+        let y = self.y;
+        for (x, (o, &rgb)) in out.iter_mut().zip(self.rows[0].iter()).enumerate() {
+            *o = self.strat.map(x as u32, y, rgb);
+        }
+        self.rows.swap(0, 1);
+        self.buffered -= 1;
+        self.y += 1;
+        Some(y)
+    }
+
+    /// Consumes the streamer, returning the strategy.
+    pub fn into_strategy(self) -> S {
+        self.strat
+    }
+}
+
+/// Quantizes a row-major RGB image, dithering rows in parallel (host-side).
+///
+/// Each row is mapped by its own clone of `strat`, which is sound because
+/// `StatelessDither` strategies keep no state between pixels.
+#[cfg(feature = "rayon")]
+pub fn par_quantize<S>(src: &[[u8; 3]], width: u32, strat: &S) -> std::vec::Vec<Spectra6>
+where
+    S: StatelessDither,
+{
+    use rayon::prelude::*;
+
+    let width = width.max(1) as usize;
+    src.par_chunks(width)
+        .enumerate()
+        .flat_map_iter(|(y, row)| {
+            let mut s = strat.clone();
+            row.iter()
+                .enumerate()
+                .map(move |(x, &rgb)| s.map(x as u32, y as u32, rgb))
+        })
+        .collect()
+}
+
+/// 4x4 Bayer thresholds 0..15
+/// Source: standard Bayer matrix
+#[cfg(feature = "dither-bayer")]
+const BAYER4X4: [[i16; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
 /// Ordered Bayer 4x4: zero-alloc, fast.
 #[cfg(feature = "dither-bayer")]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct Bayer4x4;
 
+#[cfg(feature = "dither-bayer")]
+impl StatelessDither for Bayer4x4 {}
+
+#[cfg(feature = "dither-bayer")]
+impl Bayer4x4 {
+    /// Ordered Bayer 4x4 with the bias scaled per channel, in sixteenths.
+    ///
+    /// `[16, 16, 16]` matches `Bayer4x4`. Scaling roughly by the inverse square
+    /// root of the distance weights (see [`WeightedBayer4x4::HUE_STABLE`])
+    /// nudges each channel by a similar perceived amount, which keeps
+    /// near-palette colors from drifting in hue.
+    pub fn with_channel_weights(weights: [u8; 3]) -> WeightedBayer4x4 {
+        WeightedBayer4x4 { weights }
+    }
+}
+
 #[cfg(feature = "dither-bayer")]
 impl DitherStrategy for Bayer4x4 {
     fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6 {
-        // 4x4 Bayer thresholds 0..15
-        // Source: standard Bayer matrix
-        const M: [[i16; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
-        let t = M[(y as usize) & 3][(x as usize) & 3] as i16; // 0..15
+        let t = BAYER4X4[(y as usize) & 3][(x as usize) & 3]; // 0..15
                                                               // Convert t to a small bias in -8..+7
         let bias = t - 8;
         // Apply slight luminance-ish bias equally to channels
@@ -30,6 +261,32 @@ impl DitherStrategy for Bayer4x4 {
     }
 }
 
+/// Ordered Bayer 4x4 with per-channel bias scaling.
+/// Built with [`Bayer4x4::with_channel_weights`].
+#[cfg(feature = "dither-bayer")]
+#[derive(Clone, Copy, Debug)]
+pub struct WeightedBayer4x4 {
+    weights: [u8; 3],
+}
+
+#[cfg(feature = "dither-bayer")]
+impl StatelessDither for WeightedBayer4x4 {}
+
+#[cfg(feature = "dither-bayer")]
+impl WeightedBayer4x4 {
+    /// Weights that give blue the largest and green the smallest bias.
+    pub const HUE_STABLE: [u8; 3] = [9, 7, 16];
+}
+
+#[cfg(feature = "dither-bayer")]
+impl DitherStrategy for WeightedBayer4x4 {
+    fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6 {
+        let bias = BAYER4X4[(y as usize) & 3][(x as usize) & 3] - 8;
+        let b = self.weights.map(|w| bias * w as i16 / 16);
+        map_rgb_to_spectra6_nearest(add_bias(rgb, b))
+    }
+}
+
 /// Floyd–Steinberg: keeps 2 lines of error (alloc).
 #[cfg(feature = "dither-fs")]
 pub struct FloydSteinberg {
@@ -68,11 +325,22 @@ impl FloydSteinberg {
 
 #[cfg(feature = "dither-fs")]
 impl DitherStrategy for FloydSteinberg {
+    fn reset(&mut self) {
+        self.cur.fill(0);
+        self.nxt.fill(0);
+        self.x = 0;
+        self.y = 0;
+    }
+
     fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6 {
         // Assume left-to-right scanline order. If new line, roll buffers.
-        if y != self.y || (x == 0 && self.x != 0) {
+        if y != self.y {
             self.start_line(y);
         }
+        if x >= self.width {
+            // Outside the error rows: quantize without diffusing
+            return map_rgb_to_spectra6_nearest(rgb);
+        }
         self.x = x;
         let idx = (x as usize) * 3;
         let adj = [
@@ -133,38 +401,51 @@ impl DitherStrategy for FloydSteinberg {
 pub struct Halftone {
     /// Use 2 for 2x2 tiles or 3 for 3x3.
     pub tile: u8,
+    /// Luminance thresholds between successive fill levels, ascending.
+    /// Values above 255 disable a level.
+    thresholds: [u16; 4],
 }
 
 #[cfg(feature = "halftone")]
 impl Halftone {
+    /// Evenly spaced luminance bins; the default.
+    pub const LINEAR_LEVELS: [u8; 4] = [32, 96, 160, 224];
+    /// Thresholds at the sRGB-encoded midpoints of each level's ink coverage
+    /// (gamma 2.2), so the perceived tone ramp is even instead of clumping
+    /// midtones into the darker levels.
+    pub const GAMMA_LEVELS: [u8; 4] = [99, 163, 206, 240];
+
     pub fn new(tile: u8) -> Self {
         Self {
             tile: if tile < 2 { 2 } else { tile.min(3) },
+            thresholds: Self::LINEAR_LEVELS.map(u16::from),
         }
     }
+    /// Replaces the level thresholds.
+    ///
+    /// A luminance at or above `thresholds[i]` reaches level `i + 1`. Up to
+    /// four ascending values are used; with fewer, the top levels are never
+    /// reached.
+    pub fn with_levels(mut self, thresholds: &[u8]) -> Self {
+        self.thresholds = [u16::MAX; 4];
+        for (slot, &t) in self.thresholds.iter_mut().zip(thresholds) {
+            *slot = t.into();
+        }
+        self
+    }
     #[inline]
-    fn level_from_rgb(rgb: [u8; 3]) -> u8 {
+    fn level_from_rgb(&self, rgb: [u8; 3]) -> u8 {
         // Simple luminance approximation 0..255
         let y = (3 * rgb[0] as u16 + 6 * rgb[1] as u16 + 1 * rgb[2] as u16) / 10;
-        // Map to 0, 64,128,192,255 ~ 5 levels
-        if y < 32 {
-            0
-        } else if y < 96 {
-            1
-        } else if y < 160 {
-            2
-        } else if y < 224 {
-            3
-        } else {
-            4
-        }
+        // Map to 0..=4 fill levels
+        self.thresholds.iter().filter(|&&t| y >= t).count() as u8
     }
 }
 
 #[cfg(feature = "halftone")]
 impl DitherStrategy for Halftone {
     fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> Spectra6 {
-        let lvl = Self::level_from_rgb(rgb);
+        let lvl = self.level_from_rgb(rgb);
         let n = self.tile as u32;
         let xi = (x % n) as u8;
         let yi = (y % n) as u8;
@@ -198,10 +479,145 @@ impl DitherStrategy for Halftone {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
+    #[test]
+    fn nearest_ignores_position() {
+        let mut n = Nearest;
+        assert_eq!(n.map(0, 0, [240, 20, 10]), Spectra6::Red);
+        assert_eq!(n.map(3, 9, [240, 20, 10]), Spectra6::Red);
+    }
+
+    #[test]
+    fn orange_as_red_yellow_interleaves() {
+        let mut s = OrangeAsRedYellow;
+        let orange = [255, 128, 0];
+        assert_eq!(s.map(0, 0, orange), Spectra6::Yellow);
+        assert_eq!(s.map(1, 0, orange), Spectra6::Red);
+        assert_eq!(s.map(0, 1, orange), Spectra6::Red);
+        assert_eq!(s.map(1, 1, orange), Spectra6::Yellow);
+        // Non-orange input is untouched
+        assert_eq!(s.map(1, 0, [10, 10, 240]), Spectra6::Blue);
+    }
+
+    /// Streams `src` through a `RowStreamer`, collecting the mapped pixels.
+    fn stream<S: DitherStrategy>(
+        strat: S,
+        src: &[[u8; 3]],
+        width: u32,
+    ) -> alloc::vec::Vec<Spectra6> {
+        let w = width as usize;
+        let mut streamer = RowStreamer::new(strat, width, |y, row: &mut [[u8; 3]]| {
+            match src.chunks_exact(w).nth(y as usize) {
+                Some(line) => {
+                    row.copy_from_slice(line);
+                    true
+                }
+                None => false,
+            }
+        });
+        let mut out = alloc::vec::Vec::new();
+        let mut row = alloc::vec![Spectra6::White; w];
+        let mut expected_y = 0;
+        while let Some(y) = streamer.next_row(&mut row) {
+            assert_eq!(y, expected_y);
+            expected_y += 1;
+            out.extend_from_slice(&row);
+        }
+        assert_eq!(streamer.next_row(&mut row), None);
+        out
+    }
+
+    #[test]
+    fn row_streamer_matches_buffered() {
+        let (w, h) = (7u32, 5u32);
+        let src: alloc::vec::Vec<[u8; 3]> = (0..w * h)
+            .map(|i| [(i * 37) as u8, (i * 11 + 60) as u8, (200 - i * 5) as u8])
+            .collect();
+        let buffered = |mut strat: alloc::boxed::Box<dyn DitherStrategy>| {
+            strat.reset();
+            (0..w * h)
+                .map(|i| strat.map(i % w, i / w, src[i as usize]))
+                .collect::<alloc::vec::Vec<_>>()
+        };
+
+        assert_eq!(
+            stream(Nearest, &src, w),
+            buffered(alloc::boxed::Box::new(Nearest))
+        );
+        #[cfg(feature = "dither-fs")]
+        assert_eq!(
+            stream(FloydSteinberg::new(w), &src, w),
+            buffered(alloc::boxed::Box::new(FloydSteinberg::new(w)))
+        );
+        assert!(stream(Nearest, &[], w).is_empty());
+    }
+
+    #[test]
+    fn choose_strategy_by_content() {
+        let (w, h) = (64u32, 48u32);
+        let image = |f: &dyn Fn(u32, u32) -> [u8; 3]| -> alloc::vec::Vec<[u8; 3]> {
+            (0..w * h).map(|i| f(i % w, i / w)).collect()
+        };
+
+        // Flat UI: palette colors with a few near misses
+        let flat = image(&|x, y| match (x / 16 + y / 16) % 3 {
+            0 => [250, 250, 250],
+            1 => [230, 10, 0],
+            _ => [0, 0, 0],
+        });
+        assert_eq!(choose_strategy(w, h, &flat), StrategyKind::Nearest);
+
+        // Smooth off-palette gradient
+        let gradient = image(&|x, y| [(x * 2 + 60) as u8, (y * 2 + 80) as u8, 128]);
+        assert_eq!(choose_strategy(w, h, &gradient), StrategyKind::Bayer);
+
+        // Photo-like noise
+        let mut seed = 0x1234_5678u32;
+        let noisy = image(&|_, _| [0; 3])
+            .into_iter()
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                [(seed >> 8) as u8, (seed >> 16) as u8, (seed >> 24) as u8]
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        assert_eq!(choose_strategy(w, h, &noisy), StrategyKind::FloydSteinberg);
+
+        assert_eq!(choose_strategy(0, 0, &[]), StrategyKind::Nearest);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_quantize_matches_serial() {
+        let (w, h) = (37u32, 23u32);
+        let src: std::vec::Vec<[u8; 3]> = (0..w * h)
+            .map(|i| [(i * 7) as u8, (i * 13) as u8, (i * 29) as u8])
+            .collect();
+
+        let mut serial = Nearest;
+        let expected: std::vec::Vec<Spectra6> = src
+            .iter()
+            .enumerate()
+            .map(|(i, &rgb)| serial.map(i as u32 % w, i as u32 / w, rgb))
+            .collect();
+        assert_eq!(par_quantize(&src, w, &Nearest), expected);
+
+        #[cfg(feature = "dither-bayer")]
+        {
+            let mut serial = Bayer4x4;
+            let expected: std::vec::Vec<Spectra6> = src
+                .iter()
+                .enumerate()
+                .map(|(i, &rgb)| serial.map(i as u32 % w, i as u32 / w, rgb))
+                .collect();
+            assert_eq!(par_quantize(&src, w, &Bayer4x4), expected);
+        }
+    }
+
     #[cfg(feature = "dither-bayer")]
     #[test]
     fn bayer_deterministic() {
@@ -211,6 +627,25 @@ mod tests {
         assert_eq!(a, a2);
     }
 
+    #[cfg(feature = "dither-bayer")]
+    #[test]
+    fn weighted_bayer_keeps_grays_neutral() {
+        let mut w = Bayer4x4::with_channel_weights(WeightedBayer4x4::HUE_STABLE);
+        for (x, y) in (0..4).flat_map(|y| (0..4).map(move |x| (x, y))) {
+            assert_eq!(w.map(x, y, [100, 100, 100]), Spectra6::Black);
+            assert_eq!(w.map(x, y, [200, 200, 200]), Spectra6::White);
+        }
+
+        let mut unit = Bayer4x4::with_channel_weights([16, 16, 16]);
+        let mut plain = Bayer4x4;
+        for x in 0..4 {
+            assert_eq!(
+                unit.map(x, 1, [90, 160, 30]),
+                plain.map(x, 1, [90, 160, 30])
+            );
+        }
+    }
+
     #[cfg(feature = "halftone")]
     #[test]
     fn halftone_levels() {
@@ -221,4 +656,21 @@ mod tests {
         assert!(matches!(c1, Spectra6::Black));
         assert!(matches!(c2, Spectra6::White));
     }
+
+    #[cfg(feature = "halftone")]
+    #[test]
+    fn halftone_custom_levels() {
+        let gray = [128, 128, 128];
+        // (1, 1) is the second cell to fill in a 2x2 tile
+        let mut linear = Halftone::new(2);
+        assert_eq!(linear.level_from_rgb(gray), 2);
+        assert_eq!(linear.map(1, 1, gray), Spectra6::White);
+
+        let mut gamma = Halftone::new(2).with_levels(&Halftone::GAMMA_LEVELS);
+        assert_eq!(gamma.level_from_rgb(gray), 1);
+        assert_eq!(gamma.map(1, 1, gray), Spectra6::Black);
+
+        let two = Halftone::new(2).with_levels(&[10, 20]);
+        assert_eq!(two.level_from_rgb([255, 255, 255]), 2);
+    }
 }
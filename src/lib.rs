@@ -46,7 +46,10 @@
 #![no_std]
 #![warn(missing_docs)]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 // Feature exclusivity checks for dithering strategies
 #[cfg(all(
@@ -63,25 +66,47 @@ compile_error!("Choose exactly one dither feature: dither-bayer OR dither-fs OR
 ))]
 compile_error!("Choose exactly one dither feature.");
 
-#[cfg(any(feature = "dither-bayer", feature = "dither-fs", feature = "halftone"))]
+#[cfg(feature = "pal-spectra6")]
 pub mod adapter;
-#[cfg(any(feature = "dither-bayer", feature = "dither-fs", feature = "halftone"))]
+#[cfg(feature = "async")]
+pub mod asynch;
+#[cfg(feature = "alloc")]
+pub mod builder;
+pub mod bus;
+pub mod codec;
+pub mod config;
+#[cfg(feature = "std")]
+pub mod debug;
+#[cfg(feature = "pal-spectra6")]
 pub mod dither;
 #[cfg(feature = "pal-spectra6")]
 pub mod palette;
+pub mod portrait;
+#[cfg(feature = "pal-spectra6")]
+pub mod stream;
 
-use alloc::{boxed::Box, vec};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::TryReserveError, vec, vec::Vec};
 use core::marker::PhantomData;
+use core::ops::IndexMut;
 
+#[cfg(feature = "alloc")]
+pub use builder::Gdep073e01Builder;
+pub use config::{BoardPreset, BoosterProfile, FrameRate};
+#[cfg(feature = "pal-spectra6")]
+use embedded_graphics::image::{GetPixel, ImageRaw};
+#[cfg(feature = "alloc")]
+use embedded_graphics::mono_font::MonoFont;
 use embedded_graphics::{
-    pixelcolor::{raw::RawU4, PixelColor},
+    pixelcolor::{raw::RawU4, BinaryColor, Gray2, PixelColor, Rgb565, Rgb888},
     prelude::*,
     primitives::Rectangle,
 };
+
 use embedded_hal::{
     delay::DelayNs,
     digital::{InputPin, OutputPin},
-    spi::SpiDevice,
+    spi::{Operation, SpiBus, SpiDevice},
 };
 
 /// Display width in pixels
@@ -89,7 +114,18 @@ pub const WIDTH: u32 = 800;
 /// Display height in pixels
 pub const HEIGHT: u32 = 480;
 
-const BUFFER_SIZE: usize = (WIDTH * HEIGHT / 2) as usize;
+/// `CMD_DISPLAY_REFRESH` parameter of the reference full refresh.
+///
+/// This is the only mode documented for the GDEP073E01. Other values select
+/// controller-specific waveforms; see [`Gdep073e01::set_refresh_param`].
+pub const REFRESH_FULL: u8 = 0x00;
+
+/// Size of the frame buffer in bytes, two pixels per byte.
+pub const BUFFER_SIZE: usize = (WIDTH * HEIGHT / 2) as usize;
+const STREAM_CHUNK_SIZE: usize = 256;
+/// Unused color code marking transparent overlay pixels.
+#[cfg(feature = "overlay")]
+const OVERLAY_TRANSPARENT: u8 = 0x7;
 
 // Display command constants
 const CMD_PANEL_SETTING: u8 = 0x00;
@@ -102,8 +138,14 @@ const CMD_BOOSTER_SOFT_START2: u8 = 0x06;
 const CMD_DEEP_SLEEP: u8 = 0x07;
 const CMD_BOOSTER_SOFT_START3: u8 = 0x08;
 const CMD_DATA_START_TRANSMISSION: u8 = 0x10;
+#[cfg(feature = "split-transmission")]
+const CMD_DATA_START_TRANSMISSION2: u8 = 0x13;
 const CMD_DISPLAY_REFRESH: u8 = 0x12;
 const CMD_PLL_CONTROL: u8 = 0x30;
+const CMD_TSC: u8 = 0x40;
+const CMD_PARTIAL_WINDOW: u8 = 0x90;
+const CMD_PARTIAL_IN: u8 = 0x91;
+const CMD_PARTIAL_OUT: u8 = 0x92;
 const CMD_CDI: u8 = 0x50;
 const CMD_TCON_SETTING: u8 = 0x60;
 const CMD_TRES: u8 = 0x61;
@@ -111,10 +153,36 @@ const CMD_T_VDCS: u8 = 0x84;
 const CMD_PWS: u8 = 0xE3;
 const CMD_CMDH: u8 = 0xAA;
 
+/// Default `CMD_CMDH` payload unlocking the controller's command set.
+const CMDH_DATA: [u8; 6] = [0x49, 0x55, 0x20, 0x08, 0x09, 0x18];
+
+/// Default `CMD_PANEL_SETTING` payload (PSR): gate scan up, source shift right.
+const PANEL_SETTING_DATA: [u8; 2] = [0x5F, 0x69];
+/// PSR byte 0, bit 3 (UD): gates scan up when set, down when clear.
+const PSR_GATE_UP: u8 = 1 << 3;
+/// PSR byte 0, bit 2 (SHL): sources shift right when set, left when clear.
+const PSR_SOURCE_RIGHT: u8 = 1 << 2;
+
+/// Default `CMD_CDI` payload: white border (VBD), DDX set, interval 0xF.
+const CDI_DATA: u8 = 0x3F;
+/// CDI bits 7-5 (VBD): color code the border is driven to during refresh.
+const CDI_BORDER_SHIFT: u8 = 5;
+
+/// `CMD_TRES` payload: width and height as big-endian 16-bit values (800x480).
+const TRES_DATA: [u8; 4] = [0x03, 0x20, 0x01, 0xE0];
+
+const _: () = assert!(
+    resolution_matches(TRES_DATA, BUFFER_SIZE),
+    "TRES_DATA does not match WIDTH/HEIGHT"
+);
+
 // Timing constants
-const RESET_DELAY_MS: u32 = 10;
 const BUSY_WAIT_DELAY_MS: u32 = 10;
 const BUSY_TIMEOUT_MS: u32 = 30_000;
+const BUSY_ACK_POLL_MS: u32 = 1;
+const DEFAULT_SPI_CLOCK_HZ: u32 = 4_000_000;
+#[cfg(feature = "alloc")]
+const DEFAULT_DELTA_THRESHOLD: usize = 64;
 
 /// GDEP073E01 color variants.
 ///
@@ -123,6 +191,11 @@ const BUSY_TIMEOUT_MS: u32 = 30_000;
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
 #[derive(Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Color {
     /// Black color (0x00)
     Black = 0x00,
@@ -141,10 +214,139 @@ pub enum Color {
     Green = 0x06,
 }
 
+impl Color {
+    /// Decodes a native 4-bit color code, returning `None` for unused codes.
+    pub const fn from_nibble(nibble: u8) -> Option<Color> {
+        match nibble {
+            0x00 => Some(Color::Black),
+            0x01 => Some(Color::White),
+            0x02 => Some(Color::Yellow),
+            0x03 => Some(Color::Red),
+            0x04 => Some(Color::Orange),
+            0x05 => Some(Color::Blue),
+            0x06 => Some(Color::Green),
+            _ => None,
+        }
+    }
+
+    /// Compact 3-bit code of the color, equal to its native nibble.
+    ///
+    /// See [`codec`] for packing frames at 3 bits per pixel.
+    pub const fn to_bits3(self) -> u8 {
+        self as u8
+    }
+
+    /// Decodes a 3-bit code; the unused code 7 gives `None`.
+    ///
+    /// Bits above the lowest three are ignored.
+    pub const fn from_bits3(bits: u8) -> Option<Color> {
+        Color::from_nibble(bits & 0x07)
+    }
+
+    /// Returns the following color in nibble order, wrapping `Green` to `Black`.
+    ///
+    /// Handy for bring-up patterns that cycle pixels through every color.
+    pub const fn next(self) -> Color {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Yellow,
+            Color::Yellow => Color::Red,
+            Color::Red => Color::Orange,
+            Color::Orange => Color::Blue,
+            Color::Blue => Color::Green,
+            Color::Green => Color::Black,
+        }
+    }
+
+    /// Nominal sRGB center of the pigment.
+    pub(crate) const fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Black => [0, 0, 0],
+            Color::White => [255, 255, 255],
+            Color::Yellow => [255, 255, 0],
+            Color::Red => [255, 0, 0],
+            Color::Orange => [255, 128, 0],
+            Color::Blue => [0, 0, 255],
+            Color::Green => [0, 255, 0],
+        }
+    }
+
+    /// Closest `Rgb565` to the color's nominal sRGB center, e.g. to mirror
+    /// content on a TFT.
+    pub fn to_rgb565(self) -> Rgb565 {
+        let [r, g, b] = self.rgb();
+        Rgb888::new(r, g, b).into()
+    }
+
+    /// Number of colors.
+    pub const COUNT: usize = 7;
+
+    /// Iterates over every color once, in native nibble order.
+    pub fn iter() -> impl Iterator<Item = Color> {
+        Self::ALL.into_iter()
+    }
+
+    /// All colors in native nibble order.
+    pub(crate) const ALL: [Color; Color::COUNT] = [
+        Color::Black,
+        Color::White,
+        Color::Yellow,
+        Color::Red,
+        Color::Orange,
+        Color::Blue,
+        Color::Green,
+    ];
+}
+
+impl core::fmt::Display for Color {
+    /// Writes the color's name, e.g. `Orange`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(match self {
+            Color::Black => "Black",
+            Color::White => "White",
+            Color::Yellow => "Yellow",
+            Color::Red => "Red",
+            Color::Orange => "Orange",
+            Color::Blue => "Blue",
+            Color::Green => "Green",
+        })
+    }
+}
+
 impl PixelColor for Color {
     type Raw = RawU4;
 }
 
+impl Color {
+    /// Converts a `BinaryColor` using explicit colors for the on and off states.
+    pub const fn from_binary(color: BinaryColor, on: Color, off: Color) -> Color {
+        match color {
+            BinaryColor::On => on,
+            BinaryColor::Off => off,
+        }
+    }
+}
+
+impl From<BinaryColor> for Color {
+    /// Maps `On` to `Black` and `Off` to `White`, matching ink on paper.
+    fn from(color: BinaryColor) -> Self {
+        Color::from_binary(color, Color::Black, Color::White)
+    }
+}
+
+impl From<Gray2> for Color {
+    /// Maps the four gray levels to the panel colors closest in luminance:
+    /// `Black`, `Red`, `Orange`, `White`.
+    fn from(color: Gray2) -> Self {
+        match color.luma() {
+            0 => Color::Black,
+            1 => Color::Red,
+            2 => Color::Orange,
+            _ => Color::White,
+        }
+    }
+}
+
 /// GDEP073E01 display driver.
 ///
 /// This driver manages communication with the GDEP073E01 7-color e-paper display
@@ -158,35 +360,284 @@ impl PixelColor for Color {
 /// - `RST`: Reset pin (active low)
 /// - `BUSY`: Busy indicator pin (high when display is busy)
 /// - `DELAY`: Delay provider implementing `DelayNs`
-pub struct Gdep073e01<SPI, CS, DC, RST, BUSY, DELAY> {
+///
+/// The lifetime `'a` is that of a buffer passed to
+/// [`new_with_buffer`](Self::new_with_buffer); drivers owning their buffer
+/// can use any lifetime, including `'static`.
+pub struct Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY> {
     spi: SPI,
     cs: CS,
     dc: DC,
     rst: RST,
     busy: BUSY,
     delay: DELAY,
-    buffer: Box<[u8]>,
+    buffer: FrameBuffer<'a>,
+    busy_active_high: bool,
+    reset_delay_ms: u32,
+    chunk_size: usize,
+    spi_clock_hz: u32,
+    booster_profile: BoosterProfile,
+    panel_setting: [u8; 2],
+    cdi: u8,
+    frame_rate: FrameRate,
+    cmdh: [u8; 6],
+    rotation: Rotation,
+    /// Horizontal and vertical mirroring, applied before the rotation.
+    mirror: (bool, bool),
+    refresh_param: u8,
+    pre_refresh_delay_ms: u32,
+    /// Longest BUSY wait before `Error::Timeout`; 0 waits forever.
+    busy_timeout_ms: u32,
+    /// How long a refresh may take to assert BUSY; `None` skips the check.
+    busy_ack_timeout_ms: Option<u32>,
+    #[cfg(feature = "alloc")]
+    busy_waiter: Option<BusyWaiter>,
+    #[cfg(feature = "alloc")]
+    event_sink: Option<EventSink>,
+    flush_state: FlushState,
+    init_state: InitState,
+    /// Stats of the flush in progress, completed at the end of its refresh.
+    pending_stats: FlushStats,
+    last_flush_stats: Option<FlushStats>,
+    /// Byte-wise lookup applying the color map to two packed pixels; `None` for identity.
+    color_map: Option<[u8; 256]>,
+    /// Poll steps taken by the most recent BUSY wait.
+    busy_polls: u32,
+    /// Poll steps the last power-on during init took, if any.
+    init_busy_polls: Option<u32>,
+    /// Frame last sent with a buffer flush, kept once `flush_delta` is used.
+    #[cfg(feature = "alloc")]
+    delta_shadow: Option<Box<[u8]>>,
+    /// Largest number of changed pixels `flush_delta` sends as windows.
+    #[cfg(feature = "alloc")]
+    delta_threshold: usize,
+    /// Inclusive physical corners of what was drawn since the last flush.
+    dirty: Option<(Point, Point)>,
+    /// Shutdown routine run by `Drop`, set by `set_sleep_on_drop`.
+    sleep_on_drop: Option<fn(&mut Self)>,
+    #[cfg(feature = "split-transmission")]
+    previous_frame: Option<Box<[u8]>>,
+    /// Pixels merged over the buffer at flush; `OVERLAY_TRANSPARENT` shows the buffer.
+    #[cfg(feature = "overlay")]
+    overlay: Option<Box<[u8]>>,
     _phantom: PhantomData<Color>,
 }
 
+/// User-provided routine that blocks until the BUSY line may have changed.
+#[cfg(feature = "alloc")]
+type BusyWaiter = Box<dyn FnMut(u32) -> Result<u32, ()> + Send>;
+
+/// User-provided callback receiving flush lifecycle events.
+#[cfg(feature = "alloc")]
+type EventSink = Box<dyn FnMut(FlushEvent) + Send>;
+
+/// Per-pixel threshold turning a coverage or ratio (0..=255) into on/off.
+///
+/// A pixel is set where the value is strictly greater than the threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageThreshold {
+    /// The same threshold everywhere.
+    Fixed(u8),
+    /// 4x4 ordered (Bayer) thresholds, spread over 8..=248.
+    Ordered4x4,
+}
+
+impl CoverageThreshold {
+    const ORDERED_4X4: [[u8; 4]; 4] =
+        [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+    /// Threshold at pixel `(x, y)`.
+    pub const fn at(self, x: u32, y: u32) -> u8 {
+        match self {
+            CoverageThreshold::Fixed(t) => t,
+            CoverageThreshold::Ordered4x4 => {
+                Self::ORDERED_4X4[(y & 3) as usize][(x & 3) as usize] * 16 + 8
+            }
+        }
+    }
+}
+
+/// Clockwise rotation of drawn content on the panel, see [`Gdep073e01::set_rotation`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Rotation {
+    /// Landscape, as the panel scans
+    #[default]
+    Deg0,
+    /// Portrait; logical (0, 0) is the physical top-right corner
+    Deg90,
+    /// Upside-down landscape
+    Deg180,
+    /// Portrait; logical (0, 0) is the physical bottom-left corner
+    Deg270,
+}
+
+impl Rotation {
+    /// Maps in-bounds logical coordinates to physical ones.
+    const fn to_physical(self, x: u32, y: u32) -> (u32, u32) {
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (WIDTH - 1 - y, x),
+            Rotation::Deg180 => (WIDTH - 1 - x, HEIGHT - 1 - y),
+            Rotation::Deg270 => (y, HEIGHT - 1 - x),
+        }
+    }
+
+    /// Whether logical width and height are swapped.
+    const fn is_portrait(self) -> bool {
+        matches!(self, Rotation::Deg90 | Rotation::Deg270)
+    }
+}
+
+/// Progress reported by [`Gdep073e01::flush_step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushProgress {
+    /// Frame data is still being transmitted
+    InProgress,
+    /// All data is sent and the panel is refreshing
+    Refreshing,
+    /// The refresh has finished
+    Done,
+}
+
+/// Progress reported by [`Gdep073e01::init_step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitProgress {
+    /// A reset edge or configuration command was sent; more steps follow
+    InProgress,
+    /// Power-on was sent and BUSY is still asserted
+    PoweringOn,
+    /// The panel is initialized
+    Done,
+}
+
+/// Lifecycle events reported to the sink installed with
+/// [`Gdep073e01::set_event_sink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlushEvent {
+    /// Frame data transmission is starting
+    TransmitStart {
+        /// Number of bytes that will be sent
+        bytes: usize,
+    },
+    /// Frame data transmission finished
+    TransmitEnd {
+        /// Number of bytes sent
+        bytes: usize,
+    },
+    /// The refresh command is about to be sent
+    RefreshStart,
+    /// The panel finished refreshing
+    RefreshEnd {
+        /// Time spent waiting for BUSY, in milliseconds
+        busy_ms: u32,
+    },
+    /// BUSY did not clear within the timeout
+    Timeout {
+        /// Time spent waiting for BUSY, in milliseconds
+        waited_ms: u32,
+    },
+}
+
+/// A dithered image ready to be shown, see [`Gdep073e01::bake`].
+///
+/// Holds the image's colors packed two per byte, like the frame buffer, so
+/// replaying it with [`Gdep073e01::present_baked`] skips the dithering.
+#[cfg(all(feature = "pal-spectra6", feature = "alloc"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BakedFrame {
+    width: u32,
+    height: u32,
+    /// Rows of `width.div_ceil(2)` bytes, the even-x pixel in the high nibble.
+    data: Box<[u8]>,
+}
+
+#[cfg(all(feature = "pal-spectra6", feature = "alloc"))]
+impl BakedFrame {
+    /// Image width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Image height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn color(&self, x: u32, y: u32) -> Color {
+        let byte = self.data[(y * self.width.div_ceil(2) + x / 2) as usize];
+        let nibble = if x & 1 == 0 { byte >> 4 } else { byte & 0x0F };
+        Color::from_nibble(nibble).unwrap_or_default()
+    }
+}
+
+/// Summary of the most recent blocking flush, see [`Gdep073e01::last_flush_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Frame data bytes sent
+    pub bytes: usize,
+    /// Whether only a window of the panel was refreshed
+    pub partial: bool,
+    /// Refresh time in milliseconds, derived from the BUSY poll interval
+    pub refresh_ms_est: u32,
+}
+
+/// Internal state of a resumable flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlushState {
+    Idle,
+    Transmitting { offset: usize },
+    Refreshing,
+}
+
+/// Internal state of a stepped init.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InitState {
+    Idle,
+    ResetHigh,
+    Configuring { index: usize },
+    PowerOn,
+    PoweringOn,
+}
+
 /// Error types for the GDEP073E01 driver.
+///
+/// `PinE` is the error type shared by the CS, DC and RST output pins. The
+/// BUSY input reports through its own `BusyE`, which defaults to `PinE`, so
+/// an input pin from a different HAL crate can be mixed with the outputs.
+///
+/// With the `defmt` feature it implements `defmt::Format` when the wrapped
+/// SPI and pin error types do. New variants may be added, so matches
+/// need a wildcard arm.
 #[derive(Debug)]
-pub enum Error<SpiE, PinE> {
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum Error<SpiE, PinE, BusyE = PinE> {
     /// SPI communication error
     Spi(SpiE),
     /// GPIO pin operation error
     Pin(PinE),
+    /// BUSY pin read error
+    Busy(BusyE),
     /// Timeout waiting for display ready
     Timeout,
+    /// Supplied pixel or byte data does not match the expected length
+    InvalidLength,
+    /// BUSY did not assert after a refresh command, so the panel likely ignored it
+    BusyNeverAsserted,
+    /// Image data is in a format the decoder does not support
+    UnsupportedFormat,
+    /// The waiter installed with `set_busy_waiter` returned an error
+    WaiterFailed,
 }
 
-impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE> Gdep073e01<SPI, CS, DC, RST, BUSY, DELAY>
+impl<'a, SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>
+    Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY>
 where
     SPI: SpiDevice<u8, Error = SpiE>,
     CS: OutputPin<Error = PinE>,
     DC: OutputPin<Error = PinE>,
     RST: OutputPin<Error = PinE>,
-    BUSY: InputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
     DELAY: DelayNs,
 {
     /// Creates a new GDEP073E01 driver instance.
@@ -202,10 +653,80 @@ where
     ///
     /// # Returns
     ///
-    /// A new driver instance with an initialized buffer.
+    /// A new driver instance with an initialized buffer, allocated on the
+    /// heap (feature `alloc`).
+    #[cfg(feature = "alloc")]
     pub fn new(spi: SPI, cs: CS, dc: DC, rst: RST, busy: BUSY, delay: DELAY) -> Self {
         let buffer = vec![0x11; BUFFER_SIZE].into_boxed_slice(); // Default to white
+        Self::with_buffer(spi, cs, dc, rst, busy, delay, buffer.into())
+    }
+
+    /// Creates a driver drawing into caller-provided storage.
+    ///
+    /// The frame buffer is borrowed rather than allocated, so it can live in
+    /// a `static` (for example an RTIC local resource or a `static_cell`) or
+    /// on the stack of a `main` that never returns. The buffer is cleared to
+    /// white. This is the only constructor without the `alloc` feature, and
+    /// the driver then never touches a heap; the methods that need one
+    /// (delta flushes, callbacks, `flush_bytes`, flood fill, text) are left
+    /// out.
+    ///
+    /// # Arguments
+    ///
+    /// * `buffer` - Frame buffer storage, [`BUFFER_SIZE`] bytes
+    /// * others - As for `new`
+    pub fn new_with_buffer(
+        spi: SPI,
+        cs: CS,
+        dc: DC,
+        rst: RST,
+        busy: BUSY,
+        delay: DELAY,
+        buffer: &'a mut [u8; BUFFER_SIZE],
+    ) -> Self {
+        buffer.fill(0x11);
+        Self::with_buffer(spi, cs, dc, rst, busy, delay, FrameBuffer::Borrowed(buffer))
+    }
+
+    /// Creates a new driver instance, reporting allocation failure instead of panicking.
+    ///
+    /// Same as [`new`](Self::new), but the 192 KB frame buffer is allocated
+    /// with `try_reserve_exact`, for firmware that must not abort when the
+    /// heap is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns the allocator's error if the frame buffer cannot be allocated.
+    #[cfg(feature = "alloc")]
+    pub fn try_new(
+        spi: SPI,
+        cs: CS,
+        dc: DC,
+        rst: RST,
+        busy: BUSY,
+        delay: DELAY,
+    ) -> Result<Self, TryReserveError> {
+        let buffer = try_alloc_buffer(BUFFER_SIZE)?;
+        Ok(Self::with_buffer(
+            spi,
+            cs,
+            dc,
+            rst,
+            busy,
+            delay,
+            buffer.into(),
+        ))
+    }
 
+    fn with_buffer(
+        spi: SPI,
+        cs: CS,
+        dc: DC,
+        rst: RST,
+        busy: BUSY,
+        delay: DELAY,
+        buffer: FrameBuffer<'a>,
+    ) -> Self {
         Self {
             spi,
             cs,
@@ -214,370 +735,6184 @@ where
             busy,
             delay,
             buffer,
+            busy_active_high: BoardPreset::Generic.busy_active_high(),
+            reset_delay_ms: BoardPreset::Generic.reset_delay_ms(),
+            chunk_size: BoardPreset::Generic.chunk_size(),
+            spi_clock_hz: DEFAULT_SPI_CLOCK_HZ,
+            booster_profile: BoosterProfile::Quality,
+            panel_setting: PANEL_SETTING_DATA,
+            cdi: CDI_DATA,
+            frame_rate: FrameRate::Hz50,
+            cmdh: CMDH_DATA,
+            rotation: Rotation::Deg0,
+            mirror: (false, false),
+            refresh_param: REFRESH_FULL,
+            pre_refresh_delay_ms: 0,
+            busy_timeout_ms: BUSY_TIMEOUT_MS,
+            busy_ack_timeout_ms: None,
+            #[cfg(feature = "alloc")]
+            busy_waiter: None,
+            #[cfg(feature = "alloc")]
+            event_sink: None,
+            flush_state: FlushState::Idle,
+            init_state: InitState::Idle,
+            pending_stats: FlushStats::default(),
+            last_flush_stats: None,
+            color_map: None,
+            busy_polls: 0,
+            init_busy_polls: None,
+            #[cfg(feature = "alloc")]
+            delta_shadow: None,
+            #[cfg(feature = "alloc")]
+            delta_threshold: DEFAULT_DELTA_THRESHOLD,
+            dirty: None,
+            sleep_on_drop: None,
+            #[cfg(feature = "split-transmission")]
+            previous_frame: None,
+            #[cfg(feature = "overlay")]
+            overlay: None,
             _phantom: PhantomData,
         }
     }
 
-    /// Initializes the display.
+    /// Applies the BUSY polarity, reset timing and SPI chunk size of a board preset.
     ///
-    /// Performs hardware reset and sends the initialization sequence required
-    /// for proper display operation. This must be called before any drawing operations.
+    /// # Example
     ///
-    /// # Errors
+    /// ```
+    /// # use core::convert::Infallible;
+    /// # use embedded_hal::spi::SpiDevice;
+    /// # use embedded_hal::digital::{OutputPin, InputPin};
+    /// # use embedded_hal::delay::DelayNs;
+    /// # struct MockSpi; struct MockPin; struct MockDelay;
+    /// # impl embedded_hal::spi::ErrorType for MockSpi { type Error = Infallible; }
+    /// # impl SpiDevice<u8> for MockSpi { fn transaction(&mut self, _: &mut [embedded_hal::spi::Operation<'_, u8>]) -> Result<(), Self::Error> { Ok(()) } }
+    /// # impl embedded_hal::digital::ErrorType for MockPin { type Error = Infallible; }
+    /// # impl OutputPin for MockPin { fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) } fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) } }
+    /// # impl InputPin for MockPin { fn is_high(&mut self) -> Result<bool, Self::Error> { Ok(false) } fn is_low(&mut self) -> Result<bool, Self::Error> { Ok(true) } }
+    /// # impl DelayNs for MockDelay { fn delay_ns(&mut self, _: u32) {} }
+    /// # let (spi, cs, dc, rst, busy, delay) = (MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay);
+    /// use gdep073e01::{BoardPreset, Gdep073e01};
     ///
-    /// Returns `Error::Spi` for SPI communication failures, `Error::Pin` for GPIO
-    /// errors, or `Error::Timeout` if the display doesn't respond within the timeout period.
-    pub fn init(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.reset()?;
-        self.send_init_sequence()?;
-        self.power_on()
+    /// let mut display = Gdep073e01::new(spi, cs, dc, rst, busy, delay)
+    ///     .with_defaults_for(BoardPreset::Generic);
+    /// display.init().unwrap();
+    /// ```
+    pub fn with_defaults_for(mut self, preset: BoardPreset) -> Self {
+        self.busy_active_high = preset.busy_active_high();
+        self.reset_delay_ms = preset.reset_delay_ms();
+        self.chunk_size = preset.chunk_size();
+        self
     }
 
-    /// Puts the display into deep sleep mode.
+    /// Sets how many frame bytes go into each SPI write, clamped to `1..=192_000`.
     ///
-    /// This significantly reduces power consumption. The display requires
-    /// reinitialization via `init()` to wake up from deep sleep.
+    /// Only the batching of the transfer changes, never the bytes sent.
+    /// Larger chunks suit DMA-backed SPI; smaller ones suit HALs that copy
+    /// each write onto a small stack. With a color map set, writes are
+    /// further split into 256-byte pieces. Defaults to 4096, or the value
+    /// of the board preset.
+    pub fn set_chunk_size(&mut self, size: usize) {
+        self.chunk_size = size.clamp(1, BUFFER_SIZE);
+    }
+
+    /// Tells the driver the SPI clock frequency used for transfer time estimates.
     ///
-    /// # Errors
+    /// This is only a hint and does not reconfigure the bus. Defaults to 4 MHz.
+    pub fn set_spi_clock_hint(&mut self, hz: u32) {
+        self.spi_clock_hz = hz;
+    }
+
+    /// Selects the booster soft-start settings used by the next `init()`.
     ///
-    /// Returns errors for communication failures or timeout.
-    pub fn sleep(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.power_off()?;
-        self.command_with_data(CMD_DEEP_SLEEP, &[0xA5])
+    /// Defaults to `BoosterProfile::Quality`, the vendor reference.
+    pub fn set_booster_profile(&mut self, profile: BoosterProfile) {
+        self.booster_profile = profile;
     }
 
-    /// Updates the display with the current buffer contents.
+    /// Sets the gate and source scan directions and sends the panel setting.
     ///
-    /// Sends the internal buffer to the display and triggers a refresh.
-    /// This operation may take several seconds to complete.
+    /// These are bits of the panel setting register (PSR, command 0x00),
+    /// byte 0: UD (bit 3) scans gates bottom-up when set, and SHL (bit 2)
+    /// shifts sources right when set. The defaults are `gate_up = true`,
+    /// `source_left = false`; flipping both turns the image by 180° in
+    /// hardware, at no cost per frame. Takes effect at the next refresh and
+    /// is kept across `init()`.
     ///
     /// # Errors
     ///
-    /// Returns errors for communication failures or timeout.
-    pub fn flush(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.write_command(CMD_DATA_START_TRANSMISSION)?;
-        self.write_buffer_data()?;
-        self.refresh()
+    /// Returns errors for communication failures.
+    pub fn set_scan_direction(
+        &mut self,
+        gate_up: bool,
+        source_left: bool,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let mut psr = PANEL_SETTING_DATA;
+        psr[0] &= !(PSR_GATE_UP | PSR_SOURCE_RIGHT);
+        if gate_up {
+            psr[0] |= PSR_GATE_UP;
+        }
+        if !source_left {
+            psr[0] |= PSR_SOURCE_RIGHT;
+        }
+        self.panel_setting = psr;
+        self.command_with_data(CMD_PANEL_SETTING, &psr)
     }
 
-    /// Clears the internal buffer with the specified color.
+    /// Sets the color the border around the active area is driven to.
     ///
-    /// Note: This only affects the internal buffer. Call `flush()` to update the display.
+    /// Recomputes the VBD bits (7-5) of the VCOM and data interval setting
+    /// (CDI, command 0x50) and sends it; takes effect at the next refresh
+    /// and is kept across `init()`. Defaults to `Color::White`. The border
+    /// uses the same film as the pixels, so Black, White, Yellow, Red, Blue
+    /// and Green show as expected; Orange has no waveform on the Spectra 6
+    /// film and leaves the border a muddy gray.
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `color` - The color to fill the buffer with
-    pub fn clear_buffer(&mut self, color: Color) {
-        let color_val = color as u8;
-        let packed_color = (color_val << 4) | color_val;
-        self.buffer.fill(packed_color);
+    /// Returns errors for communication failures.
+    pub fn set_border_color(&mut self, color: Color) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.cdi = cdi_for_border(color);
+        self.command_with_data(CMD_CDI, &[self.cdi])
     }
 
-    /// Sets a pixel in the internal buffer.
+    /// Sets the waveform frame rate and sends the PLL control command.
     ///
-    /// Note: This only affects the internal buffer. Call `flush()` to update the display.
+    /// Takes effect at the next refresh and is kept across `init()`.
+    /// Defaults to `FrameRate::Hz50`, the vendor reference; see
+    /// [`FrameRate`].
     ///
-    /// # Arguments
+    /// # Errors
     ///
-    /// * `x` - X coordinate (0 to WIDTH-1)
-    /// * `y` - Y coordinate (0 to HEIGHT-1)
-    /// * `color` - Pixel color
-    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
-        if x >= WIDTH || y >= HEIGHT {
-            return;
-        }
-
-        let index = (y * WIDTH + x) as usize / 2;
-        let color_val = color as u8;
-        let mut byte = self.buffer[index];
+    /// Returns errors for communication failures.
+    pub fn set_frame_rate(&mut self, rate: FrameRate) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.frame_rate = rate;
+        self.command_with_data(CMD_PLL_CONTROL, &[rate.pll_byte()])
+    }
 
-        if x % 2 == 0 {
-            byte = (byte & 0x0F) | (color_val << 4);
-        } else {
-            byte = (byte & 0xF0) | color_val;
-        }
+    /// Sets the `CMDH` payload (command 0xAA) sent first by `init()`.
+    ///
+    /// Firmware revisions of the panel expect slightly different magic
+    /// bytes; take them from the datasheet matching your panel. Defaults to
+    /// `[0x49, 0x55, 0x20, 0x08, 0x09, 0x18]`. Takes effect at the next init.
+    pub fn set_cmdh(&mut self, bytes: [u8; 6]) {
+        self.cmdh = bytes;
+    }
 
-        self.buffer[index] = byte;
+    /// Rotates drawing by a multiple of 90°, clockwise.
+    ///
+    /// Every method that draws or reads pixels takes logical coordinates:
+    /// `set_pixel`, `get_pixel`, the embedded-graphics drawing methods and
+    /// helpers such as `fill_circle`, `draw_rect_outline`, `draw_line`,
+    /// `fill_dithered`, `copy_region` and `show_image`. `size()` swaps width
+    /// and height for 90° and 270°. The buffer keeps the physical layout, so
+    /// nothing is redrawn and the flush methods are unaffected; methods that
+    /// address buffer bytes or refresh windows (`set_pixel_unchecked`,
+    /// `region_checksum`, `debug_region`, `flush_partial`, `mark_dirty`)
+    /// use physical coordinates. `Deg0` without mirroring keeps the batched
+    /// fast paths.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
     }
 
-    fn reset(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.rst.set_low().map_err(Error::Pin)?;
-        self.delay.delay_ms(RESET_DELAY_MS);
-        self.rst.set_high().map_err(Error::Pin)?;
-        self.delay.delay_ms(RESET_DELAY_MS);
-        Ok(())
+    /// Returns the rotation set with `set_rotation`.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
     }
 
-    fn send_init_sequence(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.command_with_data(CMD_CMDH, &[0x49, 0x55, 0x20, 0x08, 0x09, 0x18])?;
-        self.command_with_data(CMD_POWER_SETTING, &[0x3F])?;
-        self.command_with_data(CMD_PANEL_SETTING, &[0x5F, 0x69])?;
-        self.command_with_data(CMD_POFS, &[0x00, 0x54, 0x00, 0x44])?;
-        self.command_with_data(CMD_BOOSTER_SOFT_START1, &[0x40, 0x1F, 0x1F, 0x2C])?;
-        self.command_with_data(CMD_BOOSTER_SOFT_START2, &[0x6F, 0x1F, 0x17, 0x49])?;
-        self.command_with_data(CMD_BOOSTER_SOFT_START3, &[0x6F, 0x1F, 0x1F, 0x22])?;
-        self.command_with_data(CMD_PLL_CONTROL, &[0x08])?;
-        self.command_with_data(CMD_CDI, &[0x3F])?;
-        self.command_with_data(CMD_TCON_SETTING, &[0x02, 0x00])?;
-        self.command_with_data(CMD_TRES, &[0x03, 0x20, 0x01, 0xE0])?; // 800x480
-        self.command_with_data(CMD_T_VDCS, &[0x01])?;
-        self.command_with_data(CMD_PWS, &[0x2F])
+    /// Mirrors drawing horizontally and/or vertically, e.g. for viewing through a mirror.
+    ///
+    /// Applies to the same methods as `set_rotation`. Mirroring flips the
+    /// logical image, so with a rotation set, `horizontal` still flips
+    /// left and right as seen by the viewer. Both default to `false`.
+    pub fn set_mirror(&mut self, horizontal: bool, vertical: bool) {
+        self.mirror = (horizontal, vertical);
     }
 
-    fn write_command(&mut self, command: u8) -> Result<(), Error<SpiE, PinE>> {
-        self.dc.set_low().map_err(Error::Pin)?;
-        self.cs.set_low().map_err(Error::Pin)?;
-        let result = self.spi.write(&[command]).map_err(Error::Spi);
-        self.cs.set_high().map_err(Error::Pin)?;
-        result
+    /// Maps logical coordinates through mirroring and rotation, or `None` if out of bounds.
+    fn to_physical(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        let size = self.size();
+        if x >= size.width || y >= size.height {
+            return None;
+        }
+        let x = if self.mirror.0 { size.width - 1 - x } else { x };
+        let y = if self.mirror.1 {
+            size.height - 1 - y
+        } else {
+            y
+        };
+        Some(self.rotation.to_physical(x, y))
     }
 
-    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SpiE, PinE>> {
-        self.dc.set_high().map_err(Error::Pin)?;
-        self.cs.set_low().map_err(Error::Pin)?;
-        let result = self.spi.write(data).map_err(Error::Spi);
-        self.cs.set_high().map_err(Error::Pin)?;
-        result
+    /// Whether logical and physical coordinates coincide, allowing the batched paths.
+    fn is_untransformed(&self) -> bool {
+        self.rotation == Rotation::Deg0 && self.mirror == (false, false)
     }
 
-    fn command_with_data(&mut self, command: u8, data: &[u8]) -> Result<(), Error<SpiE, PinE>> {
-        self.write_command(command)?;
-        self.write_data(data)
+    /// Sets the data byte sent with `CMD_DISPLAY_REFRESH` by every refresh.
+    ///
+    /// The parameter selects the refresh waveform. Defaults to
+    /// [`REFRESH_FULL`]; other values depend on the controller revision and
+    /// its OTP waveforms, so only use ones your panel supplier documents.
+    pub fn set_refresh_param(&mut self, param: u8) {
+        self.refresh_param = param;
     }
 
-    fn write_buffer_data(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.dc.set_high().map_err(Error::Pin)?;
-        self.cs.set_low().map_err(Error::Pin)?;
+    /// Sets a pause between the end of data transmission and the refresh command.
+    ///
+    /// Some Good Display reference flows wait briefly after the frame data
+    /// before sending `CMD_DISPLAY_REFRESH`, which can help marginal boards.
+    /// Applies to every refresh, including `flush_step`, which blocks for it
+    /// once. Defaults to 0 (no delay).
+    pub fn set_pre_refresh_delay_ms(&mut self, ms: u32) {
+        self.pre_refresh_delay_ms = ms;
+    }
 
-        const CHUNK_SIZE: usize = 4096;
-        let mut result = Ok(());
+    /// Sets how long BUSY waits may take before failing with `Error::Timeout`.
+    ///
+    /// Defaults to 30 seconds. Cold panels can legitimately take longer,
+    /// while test benches may want failures to surface quickly. 0 disables
+    /// the timeout and waits forever.
+    pub fn set_busy_timeout_ms(&mut self, ms: u32) {
+        self.busy_timeout_ms = ms;
+    }
 
-        for chunk in self.buffer.chunks(CHUNK_SIZE) {
-            if let Err(e) = self.spi.write(chunk).map_err(Error::Spi) {
-                result = Err(e);
-                break;
-            }
-        }
+    /// Requires BUSY to assert within `timeout_ms` of every refresh command.
+    ///
+    /// A refresh takes seconds, so a panel that never reports busy most
+    /// likely missed the command. With a timeout set, the blocking flushes
+    /// poll BUSY every millisecond after `CMD_DISPLAY_REFRESH` and fail with
+    /// [`Error::BusyNeverAsserted`] if it stays idle. Disabled by default, as
+    /// some boards pulse BUSY too briefly to be sampled; `None` disables it
+    /// again.
+    pub fn set_busy_ack_timeout(&mut self, timeout_ms: Option<u32>) {
+        self.busy_ack_timeout_ms = timeout_ms;
+    }
 
-        self.cs.set_high().map_err(Error::Pin)?;
-        result
+    /// Installs a routine used instead of the delay-based BUSY poll loop.
+    ///
+    /// The waiter should block until BUSY may have changed, for example by
+    /// sleeping with `WFI` until a pin-change interrupt fires, but for no
+    /// longer than the milliseconds it is passed, the time left before the
+    /// BUSY timeout (`u32::MAX` when the timeout is disabled). It returns
+    /// the milliseconds it actually waited, measured with a clock of its own;
+    /// they count against the timeout, so report them honestly. After each
+    /// call the BUSY pin is read again. Returning `Err(())` aborts the wait
+    /// with `Error::WaiterFailed`.
+    #[cfg(feature = "alloc")]
+    pub fn set_busy_waiter<F>(&mut self, waiter: F)
+    where
+        F: FnMut(u32) -> Result<u32, ()> + Send + 'static,
+    {
+        self.busy_waiter = Some(Box::new(waiter));
     }
 
-    fn wait_until_idle(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        let mut remaining_delay = BUSY_TIMEOUT_MS;
+    /// Removes a waiter installed with `set_busy_waiter`, restoring delay-based polling.
+    #[cfg(feature = "alloc")]
+    pub fn clear_busy_waiter(&mut self) {
+        self.busy_waiter = None;
+    }
 
-        while self.busy.is_high().map_err(Error::Pin)? {
-            if remaining_delay == 0 {
-                return Err(Error::Timeout);
-            }
-            let delay_step = remaining_delay.min(BUSY_WAIT_DELAY_MS);
-            self.delay.delay_ms(delay_step);
-            remaining_delay = remaining_delay.saturating_sub(delay_step);
+    /// Remaps colors while the buffer is transmitted, e.g. to apply a UI theme.
+    ///
+    /// Each pixel of color `c` is sent as `map[c as usize]`, so the same
+    /// buffer can be shown under different themes without redrawing. The
+    /// buffer itself is not changed. Streamed pixels (`flush_from_iter`,
+    /// `deghost`) are sent as given. Defaults to the identity map.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut map = [
+    ///     Color::Black, Color::White, Color::Yellow, Color::Red,
+    ///     Color::Orange, Color::Blue, Color::Green,
+    /// ];
+    /// map[Color::Red as usize] = Color::Orange;
+    /// display.set_color_map(map);
+    /// ```
+    pub fn set_color_map(&mut self, map: [Color; 7]) {
+        self.invalidate_delta_shadow();
+        if map == Color::ALL {
+            self.color_map = None;
+            return;
         }
-
-        Ok(())
+        let nibble = |n: u8| map.get(n as usize).map_or(n, |&c| c as u8);
+        let mut table = [0u8; 256];
+        for (byte, entry) in table.iter_mut().enumerate() {
+            let byte = byte as u8;
+            *entry = (nibble(byte >> 4) << 4) | nibble(byte & 0x0F);
+        }
+        self.color_map = Some(table);
     }
 
-    fn power_on(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.write_command(CMD_POWER_ON)?;
-        self.wait_until_idle()
+    /// Sends the previous frame before the new one on every buffer flush.
+    ///
+    /// When enabled, `flush()`, `flush_with_progress()` and `flush_step()`
+    /// transmit the last flushed frame with command 0x10 and the buffer with
+    /// command 0x13, for controllers whose waveforms use old and new data to
+    /// reduce ghosting. The reference GDEP073E01 sequence only uses 0x10, so
+    /// leave this off unless your panel's waveform expects both frames.
+    ///
+    /// Enabling allocates a second frame buffer, seeded from the current
+    /// buffer (with the overlay merged, like every kept frame), so turn it
+    /// on right after a flush. Disabling frees it.
+    /// `flush_from_iter` keeps the frame it streams; the previews bypass it.
+    #[cfg(feature = "split-transmission")]
+    pub fn set_data_transmission_split(&mut self, enabled: bool) {
+        self.previous_frame = if enabled {
+            let mut frame = vec![0; BUFFER_SIZE].into_boxed_slice();
+            self.copy_sent_frame(&mut frame);
+            Some(frame)
+        } else {
+            None
+        };
     }
 
-    fn power_off(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.command_with_data(CMD_POWER_OFF, &[0x00])?;
-        self.wait_until_idle()
+    /// Sets a pixel of the overlay, which is drawn over the buffer at flush.
+    ///
+    /// The overlay is a second frame whose pixels start out transparent;
+    /// set ones replace the buffer's pixel when `flush()`, `upload()`,
+    /// `flush_with_progress()` or `flush_step()` transmit the frame, so a
+    /// HUD survives redrawing the base image and vice versa. The buffer
+    /// itself is never modified. Partial and delta flushes send the buffer
+    /// alone. Coordinates are logical, as for [`set_pixel`](Self::set_pixel);
+    /// out-of-range pixels are ignored. The first call allocates the
+    /// overlay (192 KB).
+    #[cfg(feature = "overlay")]
+    pub fn overlay_set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        let Some((x, y)) = self.to_physical(x, y) else {
+            return;
+        };
+        let point = Point::new(x as i32, y as i32);
+        self.grow_dirty(point, point);
+
+        let overlay = self.overlay.get_or_insert_with(|| {
+            alloc::vec![OVERLAY_TRANSPARENT * 0x11; BUFFER_SIZE].into_boxed_slice()
+        });
+        let index = (y * WIDTH + x) as usize / 2;
+        let shift = if x & 1 == 0 { 4 } else { 0 };
+        overlay[index] = (overlay[index] & !(0x0F << shift)) | ((color as u8) << shift);
     }
 
-    fn refresh(&mut self) -> Result<(), Error<SpiE, PinE>> {
-        self.command_with_data(CMD_DISPLAY_REFRESH, &[0x00])?;
-        self.wait_until_idle()
+    /// Makes every overlay pixel transparent again, keeping the allocation.
+    #[cfg(feature = "overlay")]
+    pub fn overlay_clear(&mut self) {
+        if let Some(overlay) = self.overlay.as_deref_mut() {
+            overlay.fill(OVERLAY_TRANSPARENT * 0x11);
+            self.mark_all_dirty();
+        }
     }
-}
 
-impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE> DrawTarget
-    for Gdep073e01<SPI, CS, DC, RST, BUSY, DELAY>
-where
-    SPI: SpiDevice<u8, Error = SpiE>,
-    CS: OutputPin<Error = PinE>,
-    DC: OutputPin<Error = PinE>,
-    RST: OutputPin<Error = PinE>,
-    BUSY: InputPin<Error = PinE>,
-    DELAY: DelayNs,
-{
-    type Color = Color;
-    type Error = Error<SpiE, PinE>;
+    /// Wraps the driver in a [`DitherDrawTarget`](adapter::DitherDrawTarget) for drawing `Rgb888`.
+    ///
+    /// Use `display_mut()` on the wrapper to reach the driver, e.g. to flush.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use core::convert::Infallible;
+    /// # use embedded_hal::spi::SpiDevice;
+    /// # use embedded_hal::digital::{OutputPin, InputPin};
+    /// # use embedded_hal::delay::DelayNs;
+    /// # struct MockSpi; struct MockPin; struct MockDelay;
+    /// # impl embedded_hal::spi::ErrorType for MockSpi { type Error = Infallible; }
+    /// # impl SpiDevice<u8> for MockSpi { fn transaction(&mut self, _: &mut [embedded_hal::spi::Operation<'_, u8>]) -> Result<(), Self::Error> { Ok(()) } }
+    /// # impl embedded_hal::digital::ErrorType for MockPin { type Error = Infallible; }
+    /// # impl OutputPin for MockPin { fn set_low(&mut self) -> Result<(), Self::Error> { Ok(()) } fn set_high(&mut self) -> Result<(), Self::Error> { Ok(()) } }
+    /// # impl InputPin for MockPin { fn is_high(&mut self) -> Result<bool, Self::Error> { Ok(false) } fn is_low(&mut self) -> Result<bool, Self::Error> { Ok(true) } }
+    /// # impl DelayNs for MockDelay { fn delay_ns(&mut self, _: u32) {} }
+    /// # let (spi, cs, dc, rst, busy, delay) = (MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay);
+    /// use embedded_graphics::{pixelcolor::Rgb888, prelude::*, primitives::*};
+    /// use gdep073e01::dither::Nearest;
+    /// use gdep073e01::Gdep073e01;
+    ///
+    /// let mut target = Gdep073e01::new(spi, cs, dc, rst, busy, delay).into_dither_target(Nearest);
+    /// Circle::new(Point::new(100, 100), 80)
+    ///     .into_styled(PrimitiveStyle::with_fill(Rgb888::new(230, 20, 10)))
+    ///     .draw(&mut target)
+    ///     .unwrap();
+    /// target.display_mut().flush().unwrap();
+    /// ```
+    #[cfg(feature = "pal-spectra6")]
+    pub fn into_dither_target<S>(self, strat: S) -> adapter::DitherDrawTarget<Self, S>
+    where
+        S: dither::DitherStrategy,
+    {
+        adapter::DitherDrawTarget::new(self, strat)
+    }
 
-    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    /// Dithers a row-major RGB image into the buffer and flushes it.
+    ///
+    /// The image is placed at the top-left corner; the rest of the buffer is
+    /// left untouched. `strat` is reset first and pixels are visited in
+    /// scanline order, so the same image and strategy always produce the
+    /// same frame, bit for bit.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Pixels in row-major order, `width` per row
+    /// * `width` - Image width in pixels
+    /// * `strat` - Dithering strategy
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `width` is zero, `src` is not a
+    /// whole number of rows, or the image does not fit the display in its
+    /// current rotation.
+    #[cfg(feature = "pal-spectra6")]
+    pub fn present_rgb<S>(
+        &mut self,
+        src: &[[u8; 3]],
+        width: u32,
+        strat: &mut S,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>>
     where
-        I: IntoIterator<Item = Pixel<Self::Color>>,
+        S: dither::DitherStrategy,
     {
-        for Pixel(coord, color) in pixels {
-            if let Ok((x, y)) = coord.try_into() {
+        let w = width as usize;
+        let logical = self.size();
+        if w == 0
+            || width > logical.width
+            || !src.len().is_multiple_of(w)
+            || src.len() / w > logical.height as usize
+        {
+            return Err(Error::InvalidLength);
+        }
+
+        strat.reset();
+        for (y, row) in src.chunks_exact(w).enumerate() {
+            for (x, &rgb) in row.iter().enumerate() {
+                let (x, y) = (x as u32, y as u32);
+                let color = strat.map(x, y, rgb).to_driver_color();
                 self.set_pixel(x, y, color);
             }
         }
-        Ok(())
+        self.flush()
     }
 
-    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
-        let area = area.intersection(&self.bounding_box());
-        if area.is_zero_sized() {
-            return Ok(());
-        }
-
+    /// Dithers a row-major RGB image once, for showing it repeatedly.
+    ///
+    /// Maps pixels exactly as [`present_rgb`](Self::present_rgb) would:
+    /// `strat` is reset first and visited in scanline order with the
+    /// coordinates the image will have on the display. The result is
+    /// shown with [`present_baked`](Self::present_baked), so an animation
+    /// pays for dithering each frame only once. The buffer is untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `src` does not hold exactly
+    /// `width * height` pixels, either is zero, or the image does not fit
+    /// the display.
+    #[cfg(all(feature = "pal-spectra6", feature = "alloc"))]
+    pub fn bake<S>(
+        &self,
+        src: &[[u8; 3]],
+        width: u32,
+        height: u32,
+        strat: &mut S,
+    ) -> Result<BakedFrame, Error<SpiE, PinE, BusyE>>
+    where
+        S: dither::DitherStrategy,
+    {
+        let (logical_width, logical_height) = match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => (WIDTH, HEIGHT),
+            Rotation::Deg90 | Rotation::Deg270 => (HEIGHT, WIDTH),
+        };
+        if width == 0
+            || height == 0
+            || width > logical_width
+            || height > logical_height
+            || src.len() != width as usize * height as usize
+        {
+            return Err(Error::InvalidLength);
+        }
+
+        let row_bytes = width.div_ceil(2) as usize;
+        let mut data = alloc::vec![0u8; row_bytes * height as usize].into_boxed_slice();
+        strat.reset();
+        for (y, (row, out)) in src
+            .chunks_exact(width as usize)
+            .zip(data.chunks_exact_mut(row_bytes))
+            .enumerate()
+        {
+            for (x, &rgb) in row.iter().enumerate() {
+                let color = strat.map(x as u32, y as u32, rgb).to_driver_color() as u8;
+                out[x / 2] |= if x & 1 == 0 { color << 4 } else { color };
+            }
+        }
+        Ok(BakedFrame {
+            width,
+            height,
+            data,
+        })
+    }
+
+    /// Copies a baked image into the buffer's top-left corner and flushes it.
+    ///
+    /// Gives the same frame as the `present_rgb` call the image was baked
+    /// for, without dithering again. The rest of the buffer is left
+    /// untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    #[cfg(all(feature = "pal-spectra6", feature = "alloc"))]
+    pub fn present_baked(&mut self, frame: &BakedFrame) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                self.set_pixel(x, y, frame.color(x, y));
+            }
+        }
+        self.flush()
+    }
+
+    /// Dithers and flushes an RGB image with a strategy picked from its content.
+    ///
+    /// The strategy comes from [`dither::choose_strategy`]: `Nearest` for flat
+    /// artwork, `Bayer4x4` for gradients, `FloydSteinberg` for photos. Since
+    /// only one dither feature can be enabled, a choice whose feature is off
+    /// falls back to `Nearest`. Otherwise behaves like `present_rgb`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `src` does not hold exactly
+    /// `width * height` pixels or the image does not fit the display.
+    #[cfg(feature = "pal-spectra6")]
+    pub fn auto_present_rgb(
+        &mut self,
+        width: u32,
+        height: u32,
+        src: &[[u8; 3]],
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if src.len() != width as usize * height as usize {
+            return Err(Error::InvalidLength);
+        }
+        match dither::choose_strategy(width, height, src) {
+            #[cfg(feature = "dither-bayer")]
+            dither::StrategyKind::Bayer => self.present_rgb(src, width, &mut dither::Bayer4x4),
+            #[cfg(feature = "dither-fs")]
+            dither::StrategyKind::FloydSteinberg => {
+                self.present_rgb(src, width, &mut dither::FloydSteinberg::new(width))
+            }
+            _ => self.present_rgb(src, width, &mut dither::Nearest),
+        }
+    }
+
+    /// Dithers an embedded-graphics `ImageRaw<Rgb888>` into the buffer and flushes it.
+    ///
+    /// The image is drawn with its top-left corner at `top_left`, in logical
+    /// coordinates (see `set_rotation`); pixels outside the display are
+    /// clipped and the rest of the buffer is left untouched. As with
+    /// `present_rgb`, `strat` is reset first and pixels are visited in
+    /// scanline order, with absolute logical display coordinates.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication or timeout failures during the flush.
+    #[cfg(feature = "pal-spectra6")]
+    pub fn show_image<S>(
+        &mut self,
+        img: &ImageRaw<'_, Rgb888>,
+        top_left: Point,
+        strat: &mut S,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        S: dither::DitherStrategy,
+    {
+        self.dither_image(top_left, img.size(), strat, |p| {
+            img.pixel(p).map(|rgb| [rgb.r(), rgb.g(), rgb.b()])
+        });
+        self.flush()
+    }
+
+    /// Decodes a baseline JPEG and dithers it into the buffer.
+    ///
+    /// Meant for photo frames reading JPEGs from storage. Only baseline and
+    /// extended sequential (non-progressive) JPEGs are accepted, with any
+    /// chroma subsampling including 4:2:0, and the image may not be larger
+    /// than the display, which bounds the decode buffer to
+    /// `WIDTH * HEIGHT * 3` bytes. Placement, clipping and dithering work as
+    /// in [`show_image`](Self::show_image); call `flush()` to show it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedFormat`] if `data` is not a JPEG this
+    /// decoder can read, e.g. progressive, corrupt or too large.
+    #[cfg(feature = "jpeg")]
+    pub fn draw_jpeg<S>(
+        &mut self,
+        data: &[u8],
+        top_left: Point,
+        strat: &mut S,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        S: dither::DitherStrategy,
+    {
+        use zune_jpeg::zune_core::{colorspace::ColorSpace, options::DecoderOptions};
+        use zune_jpeg::{ImageInfo, JpegDecoder};
+
+        let options = DecoderOptions::default()
+            .set_max_width(WIDTH as usize)
+            .set_max_height(HEIGHT as usize)
+            .jpeg_set_out_colorspace(ColorSpace::RGB);
+        let mut decoder = JpegDecoder::new_with_options(data, options);
+        decoder
+            .decode_headers()
+            .map_err(|_| Error::UnsupportedFormat)?;
+        let Some(ImageInfo {
+            width, height, sof, ..
+        }) = decoder.info()
+        else {
+            return Err(Error::UnsupportedFormat);
+        };
+        if !sof.is_sequential_dct() {
+            return Err(Error::UnsupportedFormat);
+        }
+        let rgb = decoder.decode().map_err(|_| Error::UnsupportedFormat)?;
+
+        let size = Size::new(width.into(), height.into());
+        self.dither_image(top_left, size, strat, |p| {
+            let i = (p.y as usize * width as usize + p.x as usize) * 3;
+            rgb.get(i..i + 3).map(|px| [px[0], px[1], px[2]])
+        });
+        Ok(())
+    }
+
+    /// Dithers `size` pixels from `rgb_at` into the buffer at `top_left`.
+    ///
+    /// `rgb_at` gets image-relative points in scanline order; pixels landing
+    /// outside the logical display are skipped before mapping, so stateful
+    /// strategies only see visible pixels.
+    #[cfg(feature = "pal-spectra6")]
+    fn dither_image<S, F>(&mut self, top_left: Point, size: Size, strat: &mut S, mut rgb_at: F)
+    where
+        S: dither::DitherStrategy,
+        F: FnMut(Point) -> Option<[u8; 3]>,
+    {
+        strat.reset();
+        let bounds = self.bounding_box();
+        for y in 0..size.height as i32 {
+            for x in 0..size.width as i32 {
+                let p = top_left + Point::new(x, y);
+                if !bounds.contains(p) {
+                    continue;
+                }
+                let Some(rgb) = rgb_at(Point::new(x, y)) else {
+                    continue;
+                };
+                let (px, py) = (p.x as u32, p.y as u32);
+                let color = strat.map(px, py, rgb);
+                self.set_pixel(px, py, color.to_driver_color());
+            }
+        }
+    }
+
+    /// Returns bytes sent, partial or full, and refresh time of the last blocking flush.
+    ///
+    /// Updated when a flush method's refresh completes; `None` before the
+    /// first one. Like [`FlushEvent`]s, not recorded by `flush_step`.
+    pub fn last_flush_stats(&self) -> Option<FlushStats> {
+        self.last_flush_stats
+    }
+
+    /// Installs a callback receiving [`FlushEvent`]s from the blocking flush methods.
+    ///
+    /// Events are emitted when frame data transmission starts and ends, around
+    /// each refresh, and whenever a BUSY wait times out. Timings are derived
+    /// from the BUSY poll interval rather than a clock. `flush_step` does not
+    /// emit events.
+    #[cfg(feature = "alloc")]
+    pub fn set_event_sink<F>(&mut self, sink: F)
+    where
+        F: FnMut(FlushEvent) + Send + 'static,
+    {
+        self.event_sink = Some(Box::new(sink));
+    }
+
+    /// Reads the raw BUSY pin level, ignoring the configured polarity.
+    ///
+    /// Intended for diagnosing wiring; returns `true` when the pin reads high.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Busy` if the pin cannot be read.
+    pub fn busy_level(&mut self) -> Result<bool, Error<SpiE, PinE, BusyE>> {
+        self.busy.is_high().map_err(Error::Busy)
+    }
+
+    /// Reads the controller's internal temperature sensor, in whole °C.
+    ///
+    /// Sends the temperature sensor command (`TSC`), waits for BUSY and reads
+    /// one byte back, the integer part of the reading in two's complement.
+    /// The panel is specified for refreshes between 0 and 50 °C; readings
+    /// outside that range call for a slower or conditioning refresh, or point
+    /// to a bad read. The data line must be readable (SDO/MISO wired to the
+    /// panel's SDA); on write-only boards the result is whatever the bus idles at.
+    ///
+    /// Call it after `init()`. In deep sleep the controller ignores commands
+    /// and does not drive the data line, so the byte is meaningless until the
+    /// panel is woken with `init()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn read_temperature(&mut self) -> Result<i8, Error<SpiE, PinE, BusyE>> {
+        self.write_command(CMD_TSC)?;
+        self.wait_until_idle()?;
+        let mut data = [0u8; 1];
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = self
+            .spi
+            .transaction(&mut [Operation::Read(&mut data)])
+            .map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result.map(|()| data[0] as i8)
+    }
+
+    /// Initializes the display.
+    ///
+    /// Performs hardware reset and sends the initialization sequence required
+    /// for proper display operation. This must be called before any drawing operations.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Spi` for SPI communication failures, `Error::Pin` or `Error::Busy` for GPIO
+    /// errors, or `Error::Timeout` if the display doesn't respond within the timeout period.
+    pub fn init(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.init_with(Self::send_init_sequence)
+    }
+
+    /// Initializes the display with a custom register sequence.
+    ///
+    /// Like `init()`, but after the hardware reset each `(command, data)`
+    /// pair is sent instead of the built-in sequence, for panel batches whose
+    /// supplier recommends different booster or VCOM settings. The sequence
+    /// must configure every register the panel needs; the booster profile
+    /// and scan direction settings are not applied. Power-on follows the
+    /// sequence, so it should not contain `POWER_ON` (0x04).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// display.init_with_sequence(&[
+    ///     (0xAA, &[0x49, 0x55, 0x20, 0x08, 0x09, 0x18]),
+    ///     (0x01, &[0x3F]),
+    ///     // ...
+    ///     (0xE3, &[0x2F]),
+    /// ])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as `init()`.
+    pub fn init_with_sequence(
+        &mut self,
+        cmds: &[(u8, &[u8])],
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.init_with(|display| {
+            cmds.iter()
+                .try_for_each(|&(command, data)| display.command_with_data(command, data))
+        })
+    }
+
+    /// Resets the panel, configures it through `configure` and powers it on.
+    fn init_with<F>(&mut self, configure: F) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Error<SpiE, PinE, BusyE>>,
+    {
+        self.reset()?;
+        configure(self)?;
+        self.power_on()
+    }
+
+    /// Performs one step of `init()`, for RTOS tasks that interleave other work.
+    ///
+    /// Call repeatedly until it returns `InitProgress::Done`. The first two
+    /// calls drive RST low and high, each followed by the reset delay; each
+    /// further call sends one command of the init sequence, then `POWER_ON`.
+    /// Once power-on is sent each call checks BUSY once without waiting and
+    /// returns `InitProgress::PoweringOn` while it is asserted. The bytes sent
+    /// match `init()`. As with `flush_step`, the BUSY timeout is not applied
+    /// here; the caller decides how long to keep polling.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures. The init restarts from the
+    /// reset on the next call after an error.
+    pub fn init_step(&mut self) -> Result<InitProgress, Error<SpiE, PinE, BusyE>> {
+        let result = self.advance_init();
+        if result.is_err() {
+            self.init_state = InitState::Idle;
+        }
+        result
+    }
+
+    fn advance_init(&mut self) -> Result<InitProgress, Error<SpiE, PinE, BusyE>> {
+        match self.init_state {
+            InitState::Idle => {
+                self.rst.set_low().map_err(Error::Pin)?;
+                self.delay.delay_ms(self.reset_delay_ms);
+                self.init_state = InitState::ResetHigh;
+            }
+            InitState::ResetHigh => {
+                self.rst.set_high().map_err(Error::Pin)?;
+                self.delay.delay_ms(self.reset_delay_ms);
+                self.init_state = InitState::Configuring { index: 0 };
+            }
+            InitState::Configuring { index } => {
+                // Replay the sequence, sending only the command at `index`
+                let mut count = 0;
+                init_sequence(
+                    self.cmdh,
+                    self.booster_profile,
+                    self.panel_setting,
+                    self.cdi,
+                    self.frame_rate,
+                    |command, data| {
+                        if count == index {
+                            self.command_with_data(command, data)?;
+                        }
+                        count += 1;
+                        Ok(())
+                    },
+                )?;
+                self.init_state = if index + 1 < count {
+                    InitState::Configuring { index: index + 1 }
+                } else {
+                    InitState::PowerOn
+                };
+            }
+            InitState::PowerOn => {
+                self.write_command(CMD_POWER_ON)?;
+                self.busy_polls = 0;
+                self.init_state = InitState::PoweringOn;
+                return Ok(InitProgress::PoweringOn);
+            }
+            InitState::PoweringOn => {
+                if self.busy.is_high().map_err(Error::Busy)? == self.busy_active_high {
+                    self.busy_polls += 1;
+                    return Ok(InitProgress::PoweringOn);
+                }
+                self.init_busy_polls = Some(self.busy_polls);
+                self.init_state = InitState::Idle;
+                return Ok(InitProgress::Done);
+            }
+        }
+        Ok(InitProgress::InProgress)
+    }
+
+    /// Number of BUSY poll steps the power-on of the last `init()` or `init_soft()` took.
+    ///
+    /// Each step is one delay of the poll interval (10 ms) or one call of the
+    /// busy waiter. A unit needing markedly more steps than its siblings may
+    /// have a weak panel. Recorded even when power-on timed out; `None`
+    /// until the first init.
+    pub fn last_init_busy_polls(&self) -> Option<u32> {
+        self.init_busy_polls
+    }
+
+    /// Initializes the display like `init()`, without toggling RST.
+    ///
+    /// For boards where RST is not wired to the MCU. The controller has no
+    /// software reset command, so this powers the panel off (`POF`), then
+    /// rewrites every register with the init sequence and powers on. Only
+    /// valid when the panel was not put into deep sleep, which only a RST
+    /// edge ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn init_soft(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.power_off()?;
+        self.send_init_sequence()?;
+        self.power_on()
+    }
+
+    /// Puts the display into deep sleep mode.
+    ///
+    /// This significantly reduces power consumption. The controller loses
+    /// its registers, so waking up takes a hardware reset and the full
+    /// init sequence via `init()`. For short intervals between updates,
+    /// [`standby`](Self::standby) keeps the registers and wakes with
+    /// [`resume`](Self::resume) alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn sleep(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.power_off()?;
+        self.command_with_data(CMD_DEEP_SLEEP, &[0xA5])
+    }
+
+    /// Turns the panel's charge pumps off but keeps the controller awake.
+    ///
+    /// Unlike [`sleep`](Self::sleep) this skips deep sleep, so the registers
+    /// survive and [`resume`](Self::resume) brings the panel back without a
+    /// reset or init sequence. The tradeoff is power: the controller logic
+    /// stays powered and typically draws tens of µA in standby, against
+    /// about 1 µA in deep sleep. Worth it for updates every minute or so;
+    /// prefer `sleep()` for longer intervals.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn standby(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.power_off()
+    }
+
+    /// Powers the panel back on after [`standby`](Self::standby).
+    ///
+    /// Sends only the power-on command and waits for BUSY; after deep sleep
+    /// use `init()` instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn resume(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.power_on()
+    }
+
+    /// Puts the panel into deep sleep and parks the control pins, best effort.
+    ///
+    /// CS is driven high (deselected) and DC low even if sleeping fails.
+    /// embedded-hal pins cannot be tri-stated; reconfigure them through the
+    /// HAL for the lowest leakage.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from entering deep sleep; pin errors are ignored.
+    pub fn shutdown(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let result = self.sleep();
+        let _ = self.cs.set_high();
+        let _ = self.dc.set_low();
+        result
+    }
+
+    /// Makes dropping the driver call `shutdown()`, ignoring any error.
+    ///
+    /// Off by default. Shutting down waits for BUSY, so a drop can block for
+    /// up to the BUSY timeout if the panel is stuck.
+    pub fn set_sleep_on_drop(&mut self, enabled: bool) {
+        self.sleep_on_drop = enabled.then_some(Self::shutdown_on_drop as fn(&mut Self));
+    }
+
+    fn shutdown_on_drop(&mut self) {
+        let _ = self.shutdown();
+    }
+
+    /// Updates the display with the current buffer contents.
+    ///
+    /// Sends the internal buffer to the display and triggers a refresh.
+    /// This operation may take several seconds to complete. Equivalent to
+    /// [`upload`](Self::upload) followed by [`refresh`](Self::refresh).
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.upload()?;
+        self.refresh()
+    }
+
+    /// Sends the buffer to the controller without refreshing the panel.
+    ///
+    /// The controller holds the frame until [`refresh`](Self::refresh) is
+    /// called, so the transfer can run while the CPU is otherwise idle and
+    /// the image appear at a chosen moment. Drawing after the upload does
+    /// not reach the panel until the next upload.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures.
+    pub fn upload(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.begin_frame()?;
+        self.write_buffer_data()?;
+        self.end_frame();
+        Ok(())
+    }
+
+    /// Returns the SPI writes `flush()` would make, without touching the bus.
+    ///
+    /// Each entry is one write: `true` for a command byte (DC low), `false`
+    /// for data (DC high). Frame data is split into writes exactly as the
+    /// flush splits it, honoring the chunk size, color map and, with
+    /// `split-transmission`, the kept previous frame. BUSY waits and delays
+    /// carry no bytes and are left out. Meant for golden tests of the wire
+    /// protocol; it copies the whole frame, so avoid it on the target.
+    #[cfg(feature = "alloc")]
+    pub fn flush_bytes(&self) -> Vec<(bool, Vec<u8>)> {
+        let mut wire = WireRecorder::default();
+        let map = self.color_map.as_ref();
+        wire.command(CMD_DATA_START_TRANSMISSION);
+        #[cfg(feature = "split-transmission")]
+        if let Some(previous) = self.previous_frame.as_deref() {
+            for chunk in previous.chunks(self.chunk_size) {
+                let _ = write_mapped(&mut wire, map, chunk);
+            }
+            wire.command(CMD_DATA_START_TRANSMISSION2);
+        }
+        #[cfg(feature = "overlay")]
+        let mut overlay = self.overlay.as_deref().map(|o| o.chunks(self.chunk_size));
+        for chunk in self.buffer.chunks(self.chunk_size) {
+            #[cfg(feature = "overlay")]
+            if let Some(top) = overlay.as_mut().and_then(Iterator::next) {
+                let _ = write_overlaid(&mut wire, map, top, chunk);
+                continue;
+            }
+            let _ = write_mapped(&mut wire, map, chunk);
+        }
+        wire.command(CMD_DISPLAY_REFRESH);
+        let _ = wire.write(&[self.refresh_param]);
+        wire.writes
+    }
+
+    /// Updates the display like `flush()`, reporting transmission progress.
+    ///
+    /// `progress` is called after each SPI chunk with `(bytes_sent, total_bytes,
+    /// est_remaining_ms)`, where the estimate is derived from the SPI clock hint
+    /// (see `set_spi_clock_hint`) and reaches 0 once the buffer is sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush_with_progress<F>(
+        &mut self,
+        mut progress: F,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        F: FnMut(usize, usize, u32),
+    {
+        let clock_hz = self.spi_clock_hz;
+        self.begin_frame()?;
+        self.write_buffer_data_with(|sent, total| {
+            progress(sent, total, transfer_time_ms(total - sent, clock_hz))
+        })?;
+        self.end_frame();
+        self.refresh()
+    }
+
+    /// Updates the display like `flush()`, calling `on_poll` while the panel refreshes.
+    ///
+    /// `on_poll` runs after every BUSY poll step (each `BUSY_WAIT_DELAY_MS`,
+    /// 10 ms, or each call of the waiter set with `set_busy_waiter`), so it
+    /// can feed a watchdog or animate a progress indicator during the
+    /// seconds-long refresh. Keep it short: it delays the next poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush_with_poll<F>(&mut self, on_poll: F) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        F: FnMut(),
+    {
+        self.upload()?;
+        self.refresh_with(on_poll)
+    }
+
+    /// Performs one bounded slice of a flush, for cooperative schedulers.
+    ///
+    /// Call repeatedly until it returns `FlushProgress::Done`. While
+    /// transmitting, SPI chunks are sent as long as `budget()` returns true
+    /// (at least one chunk per call so progress is always made). Once the
+    /// buffer is sent the refresh is started and each further call checks
+    /// BUSY once without waiting. The BUSY timeout is not applied here; the
+    /// caller decides how long to keep polling. Drawing while a flush is in
+    /// progress changes the data still to be sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures. The flush restarts from
+    /// the beginning on the next call after an error.
+    pub fn flush_step<F>(&mut self, budget: F) -> Result<FlushProgress, Error<SpiE, PinE, BusyE>>
+    where
+        F: Fn() -> bool,
+    {
+        let result = self.advance_flush(budget);
+        if result.is_err() {
+            self.flush_state = FlushState::Idle;
+        }
+        result
+    }
+
+    fn advance_flush<F>(&mut self, budget: F) -> Result<FlushProgress, Error<SpiE, PinE, BusyE>>
+    where
+        F: Fn() -> bool,
+    {
+        match self.flush_state {
+            FlushState::Idle => {
+                self.begin_frame()?;
+                self.flush_state = FlushState::Transmitting { offset: 0 };
+                self.advance_flush(budget)
+            }
+            FlushState::Transmitting { mut offset } => {
+                loop {
+                    let end = (offset + self.chunk_size).min(BUFFER_SIZE);
+                    self.dc.set_high().map_err(Error::Pin)?;
+                    self.cs.set_low().map_err(Error::Pin)?;
+                    let (map, data) = (self.color_map.as_ref(), &self.buffer[offset..end]);
+                    #[cfg(feature = "overlay")]
+                    let result = match self.overlay.as_deref() {
+                        Some(overlay) => {
+                            write_overlaid(&mut self.spi, map, &overlay[offset..end], data)
+                        }
+                        None => write_mapped(&mut self.spi, map, data),
+                    };
+                    #[cfg(not(feature = "overlay"))]
+                    let result = write_mapped(&mut self.spi, map, data);
+                    let result = result.map_err(Error::Spi);
+                    self.cs.set_high().map_err(Error::Pin)?;
+                    result?;
+                    offset = end;
+                    if offset == BUFFER_SIZE || !budget() {
+                        break;
+                    }
+                }
+                if offset < BUFFER_SIZE {
+                    self.flush_state = FlushState::Transmitting { offset };
+                    return Ok(FlushProgress::InProgress);
+                }
+                self.end_frame();
+                self.start_refresh()?;
+                self.flush_state = FlushState::Refreshing;
+                Ok(FlushProgress::Refreshing)
+            }
+            FlushState::Refreshing => {
+                if self.busy.is_high().map_err(Error::Busy)? == self.busy_active_high {
+                    return Ok(FlushProgress::Refreshing);
+                }
+                self.flush_state = FlushState::Idle;
+                Ok(FlushProgress::Done)
+            }
+        }
+    }
+
+    /// Streams pixels from an iterator to the display and triggers a refresh.
+    ///
+    /// Pixels are packed two per byte on the fly and sent in small chunks, so
+    /// the internal buffer is neither read nor modified. This suits streaming
+    /// decoders that produce pixels in row-major order.
+    ///
+    /// # Arguments
+    ///
+    /// * `pixels` - Pixel colors in row-major order
+    /// * `count` - Number of pixels the iterator will yield; must equal `WIDTH * HEIGHT`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidLength` if `count` is wrong or the iterator ends
+    /// early (in which case no refresh is triggered), or errors for
+    /// communication failures or timeout.
+    pub fn flush_from_iter<I>(
+        &mut self,
+        pixels: I,
+        count: usize,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        I: Iterator<Item = Color>,
+    {
+        if count != (WIDTH * HEIGHT) as usize {
+            return Err(Error::InvalidLength);
+        }
+
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+        self.write_packed_pixels(pixels)?;
+        self.refresh()
+    }
+
+    /// Cycles the panel through every color full-screen to remove ghosting.
+    ///
+    /// Each of the seven colors is streamed to the panel and refreshed in turn,
+    /// then the buffer, left untouched, is flushed so the panel shows it
+    /// again. This takes eight full refreshes (well over a minute) and is
+    /// intended for first boot or after long storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn deghost(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let count = (WIDTH * HEIGHT) as usize;
+        for color in Color::ALL {
+            self.flush_from_iter(core::iter::repeat_n(color, count), count)?;
+        }
+        self.flush()
+    }
+
+    /// Clears the buffer to `color` and flushes it.
+    ///
+    /// Shorthand for `clear_buffer` followed by `flush`, so the color map,
+    /// overlay and split transmission apply as usual. The full frame is sent:
+    /// the GDEP073E01 controller has no documented data-less clear or fill
+    /// command.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn clear_and_flush(&mut self, color: Color) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.clear_buffer(color);
+        self.flush()
+    }
+
+    /// Sends a half-resolution preview of the buffer and refreshes.
+    ///
+    /// Only even rows are read; each is transmitted twice so it also fills
+    /// the odd row below it. The full `BUFFER_SIZE` still goes over SPI, so
+    /// this saves no transfer time and no refresh time by itself. It is a
+    /// hack for perceived responsiveness that only helps with a waveform (LUT)
+    /// that tolerates a quick low-res pass. Follow up with a full `flush()`.
+    /// The buffer is not modified.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush_interlaced_preview(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        const ROW_BYTES: usize = WIDTH as usize / 2;
+
+        self.invalidate_delta_shadow();
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+        self.emit(FlushEvent::TransmitStart { bytes: BUFFER_SIZE });
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = (0..HEIGHT as usize)
+            .try_for_each(|y| {
+                let start = (y & !1) * ROW_BYTES;
+                write_mapped(
+                    &mut self.spi,
+                    self.color_map.as_ref(),
+                    &self.buffer[start..start + ROW_BYTES],
+                )
+            })
+            .map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes: BUFFER_SIZE });
+        self.refresh()
+    }
+
+    /// Sends the buffer rotated by 180° and refreshes, leaving the buffer untouched.
+    ///
+    /// Bytes are sent in reverse order with their two pixels swapped, so the
+    /// last pixel goes first. Useful for occasional upside-down mounting
+    /// without keeping rotation state. Like `flush_interlaced_preview`, this
+    /// always sends a single frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush_flipped(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.invalidate_delta_shadow();
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+        self.emit(FlushEvent::TransmitStart { bytes: BUFFER_SIZE });
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+
+        let mut staged = [0u8; STREAM_CHUNK_SIZE];
+        let result = self
+            .buffer
+            .rchunks(STREAM_CHUNK_SIZE)
+            .try_for_each(|part| {
+                for (out, &byte) in staged.iter_mut().zip(part.iter().rev()) {
+                    *out = byte.rotate_left(4);
+                }
+                write_mapped(
+                    &mut self.spi,
+                    self.color_map.as_ref(),
+                    &staged[..part.len()],
+                )
+            })
+            .map_err(Error::Spi);
+
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes: BUFFER_SIZE });
+        self.refresh()
+    }
+
+    /// Sends a buffer holding a portrait frame and refreshes.
+    ///
+    /// The buffer is read in the layout of
+    /// [`PortraitGdep073e01`](portrait::PortraitGdep073e01): logical
+    /// `(x, y)` is physical `(y, HEIGHT - 1 - x)`. Each physical row is
+    /// gathered from one logical column into a 400-byte stack buffer.
+    pub(crate) fn flush_portrait(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        const LOGICAL_ROW_BYTES: usize = HEIGHT as usize / 2;
+
+        self.invalidate_delta_shadow();
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+        self.emit(FlushEvent::TransmitStart { bytes: BUFFER_SIZE });
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+
+        let mut staged = [0u8; WIDTH as usize / 2];
+        let result = (0..HEIGHT as usize)
+            .try_for_each(|y| {
+                let logical_x = HEIGHT as usize - 1 - y;
+                let column = logical_x / 2;
+                let shift = if logical_x & 1 == 0 { 4 } else { 0 };
+                for (i, out) in staged.iter_mut().enumerate() {
+                    let even = self.buffer[2 * i * LOGICAL_ROW_BYTES + column] >> shift;
+                    let odd = self.buffer[(2 * i + 1) * LOGICAL_ROW_BYTES + column] >> shift;
+                    *out = (even << 4) | (odd & 0x0F);
+                }
+                write_mapped(&mut self.spi, self.color_map.as_ref(), &staged)
+            })
+            .map_err(Error::Spi);
+
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes: BUFFER_SIZE });
+        self.refresh()
+    }
+
+    /// Updates only the pixels changed since the last buffer flush, when few changed.
+    ///
+    /// Keeps a shadow of the last frame sent (a second 192 KB allocation,
+    /// made on first use). If at most the delta threshold (see
+    /// `set_delta_threshold`, default 64) pixels differ, the bounding box of
+    /// the changed bytes is sent through
+    /// [`flush_partial`](Self::flush_partial) and refreshed once; otherwise
+    /// this is a full `flush()`. The first call,
+    /// and the first after a flush that bypasses the buffer (streaming,
+    /// preview, flipped) or a color map change, is always a full flush.
+    /// Does nothing if no pixel changed.
+    ///
+    /// The partial window commands (0x90-0x92) follow the UC81xx convention
+    /// and are not part of the GDEP073E01 reference sequence; check that
+    /// your panel honors them before relying on this.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout. The shadow is
+    /// dropped on error, so the next call sends the full frame.
+    #[cfg(feature = "alloc")]
+    pub fn flush_delta(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let Some(mut shadow) = self.delta_shadow.take() else {
+            self.flush()?;
+            self.delta_shadow = Some(Box::from(&self.buffer[..]));
+            return Ok(());
+        };
+
+        const ROW_BYTES: usize = WIDTH as usize / 2;
+        let mut pixels = 0;
+        let (mut top_left, mut bottom_right) = ((usize::MAX, usize::MAX), (0, 0));
+        for (index, (&old, &new)) in shadow.iter().zip(self.buffer.iter()).enumerate() {
+            let diff = old ^ new;
+            if diff != 0 {
+                pixels += usize::from(diff & 0xF0 != 0) + usize::from(diff & 0x0F != 0);
+                if pixels > self.delta_threshold {
+                    break;
+                }
+                let (column, row) = (index % ROW_BYTES, index / ROW_BYTES);
+                top_left = (top_left.0.min(column), top_left.1.min(row));
+                bottom_right = (bottom_right.0.max(column), bottom_right.1.max(row));
+            }
+        }
+        if pixels == 0 {
+            self.delta_shadow = Some(shadow);
+            return Ok(());
+        }
+        if pixels > self.delta_threshold {
+            self.flush()?;
+            shadow.copy_from_slice(&self.buffer);
+            self.delta_shadow = Some(shadow);
+            return Ok(());
+        }
+
+        // Every changed byte lies in this box, so the panel matches the
+        // buffer once it is sent
+        self.flush_partial(Rectangle::with_corners(
+            Point::new(top_left.0 as i32 * 2, top_left.1 as i32),
+            Point::new(bottom_right.0 as i32 * 2 + 1, bottom_right.1 as i32),
+        ))?;
+        shadow.copy_from_slice(&self.buffer);
+        self.delta_shadow = Some(shadow);
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Sends and refreshes only the buffer contents inside `area`.
+    ///
+    /// `area` is in physical coordinates, ignoring the rotation. It is
+    /// clamped to the display and its x range widened to even bounds, since
+    /// two pixels share a byte; an empty intersection sends nothing. The
+    /// bytes go out in one partial window (commands 0x90-0x92, see the note
+    /// on [`flush_delta`](Self::flush_delta)). A controller that ignores the
+    /// window commands takes the data at the frame start and performs a full
+    /// refresh instead, so test on your panel first.
+    ///
+    /// Partial refreshes skip the full clearing waveform and leave faint
+    /// ghosting that builds up with each update of the same area. Do a full
+    /// `flush()` at least every 5 partial refreshes, and whenever the
+    /// ghosting becomes visible.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush_partial(&mut self, area: Rectangle) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let area = area.intersection(&Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT)));
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        const ROW_BYTES: usize = WIDTH as usize / 2;
+        let (x0, x1) = (
+            area.top_left.x as usize / 2,
+            bottom_right.x as usize / 2 + 1,
+        );
+        let (y0, y1) = (area.top_left.y as usize, bottom_right.y as usize + 1);
+        let bytes = (x1 - x0) * (y1 - y0);
+
+        let [x0_hi, x0_lo] = ((x0 * 2) as u16).to_be_bytes();
+        let [x1_hi, x1_lo] = ((x1 * 2 - 1) as u16).to_be_bytes();
+        let [y0_hi, y0_lo] = (y0 as u16).to_be_bytes();
+        let [y1_hi, y1_lo] = ((y1 - 1) as u16).to_be_bytes();
+        self.write_command(CMD_PARTIAL_IN)?;
+        self.command_with_data(
+            CMD_PARTIAL_WINDOW,
+            &[x0_hi, x0_lo, x1_hi, x1_lo, y0_hi, y0_lo, y1_hi, y1_lo, 0x01],
+        )?;
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+        self.emit(FlushEvent::TransmitStart { bytes });
+        self.pending_stats.partial = true;
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = (y0..y1)
+            .try_for_each(|y| {
+                let row = &self.buffer[y * ROW_BYTES + x0..y * ROW_BYTES + x1];
+                write_mapped(&mut self.spi, self.color_map.as_ref(), row)
+            })
+            .map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes });
+        self.refresh()?;
+        self.write_command(CMD_PARTIAL_OUT)?;
+
+        // The window on the panel now matches the buffer
+        if let Some((top_left, bottom_right)) = self.dirty {
+            let sent = Rectangle::new(
+                Point::new(x0 as i32 * 2, y0 as i32),
+                Size::new((x1 - x0) as u32 * 2, (y1 - y0) as u32),
+            );
+            if sent.contains(top_left) && sent.contains(bottom_right) {
+                self.dirty = None;
+            }
+        }
+        #[cfg(feature = "alloc")]
+        for y in y0..y1 {
+            let range = y * ROW_BYTES + x0..y * ROW_BYTES + x1;
+            if let Some(shadow) = self.delta_shadow.as_deref_mut() {
+                shadow[range.clone()].copy_from_slice(&self.buffer[range.clone()]);
+            }
+            #[cfg(feature = "split-transmission")]
+            if let Some(previous) = self.previous_frame.as_deref_mut() {
+                previous[range.clone()].copy_from_slice(&self.buffer[range]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Partially flushes the bounding box of everything drawn since the last flush.
+    ///
+    /// Drawing, clearing and loading the buffer grow a dirty rectangle;
+    /// this sends it through [`flush_partial`](Self::flush_partial), with
+    /// the same ghosting caveats, and empties it. Does nothing if nothing
+    /// was drawn. The full flushes, `flush_delta`, and a `flush_partial`
+    /// covering the whole dirty area empty it as well. The box covers what
+    /// was drawn, not what changed: redrawing a pixel in its current color
+    /// still marks it.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout. The dirty
+    /// area is kept on error, so the next call retries it.
+    pub fn flush_dirty(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        let Some((top_left, bottom_right)) = self.dirty else {
+            return Ok(());
+        };
+        self.flush_partial(Rectangle::with_corners(top_left, bottom_right))?;
+        self.dirty = None;
+        Ok(())
+    }
+
+    /// Adds `area`, clipped to the display, to the dirty rectangle.
+    ///
+    /// For areas the next partial flush must send although nothing was
+    /// drawn there, e.g. after the panel was disturbed. Coordinates are
+    /// physical. See [`flush_dirty`](Self::flush_dirty).
+    pub fn mark_dirty(&mut self, area: &Rectangle) {
+        let area = area.intersection(&Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT)));
+        if let Some(bottom_right) = area.bottom_right() {
+            self.grow_dirty(area.top_left, bottom_right);
+        }
+    }
+
+    /// Returns the dirty rectangle and empties it, for driving your own partial flushes.
+    ///
+    /// The rectangle is the physical bounding box of everything drawn
+    /// since the last flush or `take_dirty`, as used by
+    /// [`flush_dirty`](Self::flush_dirty); `None` if nothing was drawn.
+    /// Tracking costs a min/max update per draw call whether or not this
+    /// is used.
+    pub fn take_dirty(&mut self) -> Option<Rectangle> {
+        self.dirty
+            .take()
+            .map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+    }
+
+    /// Grows the dirty area to include the inclusive physical corners given.
+    fn grow_dirty(&mut self, top_left: Point, bottom_right: Point) {
+        self.dirty = Some(match self.dirty {
+            Some((a, b)) => (a.component_min(top_left), b.component_max(bottom_right)),
+            None => (top_left, bottom_right),
+        });
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.grow_dirty(
+            Point::zero(),
+            Point::new(WIDTH as i32 - 1, HEIGHT as i32 - 1),
+        );
+    }
+
+    /// Sets how many changed pixels `flush_delta` may send as partial windows.
+    ///
+    /// Above this count it falls back to a full flush. Defaults to 64.
+    #[cfg(feature = "alloc")]
+    pub fn set_delta_threshold(&mut self, pixels: usize) {
+        self.delta_threshold = pixels;
+    }
+
+    /// Clears the internal buffer with the specified color.
+    ///
+    /// Note: This only affects the internal buffer. Call `flush()` to update the display.
+    /// The controller has no data-less clear, so clearing the panel always
+    /// transmits a full frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color to fill the buffer with
+    pub fn clear_buffer(&mut self, color: Color) {
+        let color_val = color as u8;
+        let packed_color = (color_val << 4) | color_val;
+        self.buffer.fill(packed_color);
+        self.mark_all_dirty();
+    }
+
+    /// Replaces the internal buffer with an already packed frame.
+    ///
+    /// `data` is copied as is, in the driver layout (two pixels per byte,
+    /// the even x pixel in the high nibble, rows of `WIDTH / 2` bytes), so
+    /// frames prepared on a host need no per-pixel work. Rotation and
+    /// mirroring are not applied. Call `flush()` to update the display.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidLength`, leaving the buffer untouched, if
+    /// `data` is not exactly `WIDTH * HEIGHT / 2` bytes.
+    pub fn load_buffer(&mut self, data: &[u8]) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if data.len() != BUFFER_SIZE {
+            return Err(Error::InvalidLength);
+        }
+        self.buffer.copy_from_slice(data);
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Replaces the internal buffer with a frame packed at 3 bits per pixel.
+    ///
+    /// `data` holds every pixel in physical row-major order, as produced by
+    /// [`codec::pack_3bpp`], and is expanded into the 4-bit buffer; like
+    /// [`load_buffer`](Self::load_buffer), rotation and mirroring are not
+    /// applied. Call `flush()` to update the display.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidLength`, leaving the buffer untouched, if
+    /// `data` is not exactly `WIDTH * HEIGHT * 3 / 8` bytes.
+    pub fn load_3bpp(&mut self, data: &[u8]) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if data.len() != codec::packed_3bpp_len(BUFFER_SIZE * 2) {
+            return Err(Error::InvalidLength);
+        }
+        let mut codes = codec::codes_3bpp(data);
+        for byte in self.buffer.iter_mut() {
+            let hi = codes.next().unwrap_or(0);
+            let lo = codes.next().unwrap_or(0);
+            *byte = (hi << 4) | lo;
+        }
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Sets a pixel in the internal buffer.
+    ///
+    /// Note: This only affects the internal buffer. Call `flush()` to update the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate (0 to WIDTH-1)
+    /// * `y` - Y coordinate (0 to HEIGHT-1)
+    /// * `color` - Pixel color
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        let Some((x, y)) = self.to_physical(x, y) else {
+            return;
+        };
+        let point = Point::new(x as i32, y as i32);
+        self.grow_dirty(point, point);
+
+        let index = (y * WIDTH + x) as usize / 2;
+        self.buffer[index] = pack_nibble(self.buffer[index], x, color);
+    }
+
+    /// Reads a pixel back from the internal buffer.
+    ///
+    /// Coordinates are logical, as for [`set_pixel`](Self::set_pixel).
+    /// Returns `None` outside the display or if the pixel holds an unused
+    /// color code (e.g. from `load_buffer`).
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        let (x, y) = self.to_physical(x, y)?;
+        let point = Point::new(x as i32, y as i32);
+        self.nibble_at(point).and_then(Color::from_nibble)
+    }
+
+    /// Hashes the pixels of `area`, clipped to the display, into a 32-bit checksum.
+    ///
+    /// Coordinates are physical, as for [`flush_partial`](Self::flush_partial),
+    /// so the checksum covers exactly the pixels a partial flush of `area`
+    /// would send. Pixels are decoded row by row and hashed with FNV-1a;
+    /// equal checksums mean the region is unchanged with high probability.
+    pub fn region_checksum(&self, area: &Rectangle) -> u32 {
+        let area = area.intersection(&Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT)));
+        area.points()
+            .filter_map(|point| self.nibble_at(point))
+            .fold(0x811C_9DC5, |hash, nibble| {
+                (hash ^ u32::from(nibble)).wrapping_mul(0x0100_0193)
+            })
+    }
+
+    /// Sets a pixel in the internal buffer without bounds checking.
+    ///
+    /// For hot loops whose coordinates are already validated, where the
+    /// check in [`set_pixel`](Self::set_pixel) is measurable. Coordinates
+    /// are physical, ignoring the rotation. Debug builds still assert the
+    /// bounds.
+    ///
+    /// # Safety
+    ///
+    /// `x` must be less than [`WIDTH`] and `y` less than [`HEIGHT`].
+    /// Out-of-range coordinates are undefined behavior.
+    pub unsafe fn set_pixel_unchecked(&mut self, x: u32, y: u32, color: Color) {
+        debug_assert!(x < WIDTH && y < HEIGHT, "pixel ({x}, {y}) out of bounds");
+        let point = Point::new(x as i32, y as i32);
+        self.grow_dirty(point, point);
+
+        let index = (y * WIDTH + x) as usize / 2;
+        let shift = if x & 1 == 0 { 4 } else { 0 };
+        // SAFETY: the caller guarantees x < WIDTH and y < HEIGHT, so
+        // index < WIDTH * HEIGHT / 2, the buffer length.
+        let byte = unsafe { self.buffer.get_unchecked_mut(index) };
+        *byte = (*byte & !(0x0F << shift)) | ((color as u8) << shift);
+    }
+
+    /// Formats a region of the buffer as an ASCII color grid for logs.
+    ///
+    /// Each pixel prints as one letter (`K`lack, `W`hite, `Y`ellow, `R`ed,
+    /// `O`range, `B`lue, `G`reen, `?` for unused codes), one line per row.
+    /// The area is in physical coordinates and clipped to the display.
+    /// Formatting reads the buffer
+    /// directly and never allocates, so keep regions small on constrained
+    /// log transports.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// log::debug!("{:?}", display.debug_region(&Rectangle::new(Point::zero(), Size::new(8, 4))));
+    /// ```
+    pub fn debug_region(&self, area: &Rectangle) -> impl core::fmt::Debug + '_ {
+        BufferRegion {
+            buffer: &self.buffer,
+            area: area.intersection(&Rectangle::new(Point::zero(), Size::new(WIDTH, HEIGHT))),
+        }
+    }
+
+    /// Replaces a `ratio / 255` share of pixels with those from `target`.
+    ///
+    /// Pixels are chosen by a 4x4 ordered threshold, so calling this repeatedly
+    /// with increasing `ratio` animates a dissolve from the current content to
+    /// `target`; 0 changes nothing and 255 copies `target` completely.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Packed buffer in the driver layout, `WIDTH * HEIGHT / 2` bytes
+    /// * `ratio` - Share of pixels taken from `target`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidLength` if `target` has the wrong size.
+    pub fn dissolve_toward(
+        &mut self,
+        target: &[u8],
+        ratio: u8,
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        const THRESHOLD: CoverageThreshold = CoverageThreshold::Ordered4x4;
+
+        if target.len() != BUFFER_SIZE {
+            return Err(Error::InvalidLength);
+        }
+
+        for (i, (byte, &new)) in self.buffer.iter_mut().zip(target).enumerate() {
+            let x = (i * 2) as u32 % WIDTH;
+            let y = (i * 2) as u32 / WIDTH;
+            let mut mask = 0u8;
+            if ratio > THRESHOLD.at(x, y) {
+                mask |= 0xF0;
+            }
+            if ratio > THRESHOLD.at(x + 1, y) {
+                mask |= 0x0F;
+            }
+            *byte = (*byte & !mask) | (new & mask);
+        }
+        self.mark_all_dirty();
+        Ok(())
+    }
+
+    /// Fills an area with an ordered blend of two colors.
+    ///
+    /// Each pixel takes `b` where `ratio` exceeds the 4x4 ordered threshold
+    /// and `a` elsewhere, so `ratio / 255` is the share of `b`: 0 fills with
+    /// `a`, 255 with `b`, and 128 gives an even checkerboard. The area is in
+    /// logical coordinates and clipped to the display; the pattern is
+    /// anchored to them, so adjacent fills line up.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Rectangle to fill
+    /// * `a` - Color where the threshold is not exceeded
+    /// * `b` - Color where the threshold is exceeded
+    /// * `ratio` - Share of pixels painted with `b`
+    pub fn fill_dithered(&mut self, area: &Rectangle, a: Color, b: Color, ratio: u8) {
+        const THRESHOLD: CoverageThreshold = CoverageThreshold::Ordered4x4;
+
+        let area = area.intersection(&self.bounding_box());
+        for p in area.points() {
+            let (x, y) = (p.x as u32, p.y as u32);
+            let color = if ratio > THRESHOLD.at(x, y) { b } else { a };
+            self.set_pixel(x, y, color);
+        }
+    }
+
+    /// Replaces the connected region of the seed's color with `new`.
+    ///
+    /// Pixels are connected through their four neighbors. Works span by span
+    /// on the packed buffer with a heap-allocated stack of pending seeds, so
+    /// large regions need no recursion. `seed` is in logical coordinates
+    /// (see `set_rotation`); nothing happens if it lies outside the display
+    /// or already has color `new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Point inside the region to fill
+    /// * `new` - Fill color
+    #[cfg(feature = "alloc")]
+    pub fn flood_fill(&mut self, seed: Point, new: Color) {
+        let Some((x, y)) = <(u32, u32)>::try_from(seed)
+            .ok()
+            .and_then(|(x, y)| self.to_physical(x, y))
+        else {
+            return;
+        };
+        let target = self.nibble_at(Point::new(x as i32, y as i32));
+        if target == Some(new as u8) {
+            return;
+        }
+        let matches = |display: &Self, x: u32, y: u32| {
+            display.nibble_at(Point::new(x as i32, y as i32)) == target
+        };
+
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            if !matches(self, x, y) {
+                continue;
+            }
+            let (mut x0, mut x1) = (x, x);
+            while x0 > 0 && matches(self, x0 - 1, y) {
+                x0 -= 1;
+            }
+            while x1 + 1 < WIDTH && matches(self, x1 + 1, y) {
+                x1 += 1;
+            }
+            self.fill_span(x0, x1 + 1, y, new);
+
+            // Queue the start of every matching run above and below
+            for ny in [y.wrapping_sub(1), y + 1] {
+                if ny >= HEIGHT {
+                    continue;
+                }
+                let mut in_run = false;
+                for nx in x0..=x1 {
+                    let hit = matches(self, nx, ny);
+                    if hit && !in_run {
+                        stack.push((nx, ny));
+                    }
+                    in_run = hit;
+                }
+            }
+        }
+    }
+
+    /// Draws a filled circle directly into the internal buffer.
+    ///
+    /// Fills the shape of the midpoint circle algorithm as one horizontal
+    /// span per row, so interior bytes are written whole. Each span's width
+    /// is computed directly, so only rows on the display are visited, however
+    /// large the radius or far off the center. `center` is in logical
+    /// coordinates and the circle is clipped to the display; with a rotation
+    /// or mirroring set, pixels are written one by one.
+    ///
+    /// # Arguments
+    ///
+    /// * `center` - Circle center
+    /// * `radius` - Radius in pixels; 0 draws a single pixel
+    /// * `color` - Fill color
+    pub fn fill_circle(&mut self, center: Point, radius: u32, color: Color) {
+        // i64 holds the center plus or minus any radius
+        let r = i64::from(radius);
+        let (cx, cy) = (i64::from(center.x), i64::from(center.y));
+        let height = i64::from(self.size().height);
+        let clamp = |v: i64| v.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+
+        for y in (cy - r).max(0)..=(cy + r).min(height - 1) {
+            let half = circle_half_width(u64::from(radius), (y - cy).unsigned_abs()) as i64;
+            self.logical_hline(clamp(cx - half), clamp(cx + half), y as i32, color);
+        }
+    }
+
+    /// Draws a rectangle outline directly into the internal buffer.
+    ///
+    /// The border grows inwards from `area`; a thickness of at least half the
+    /// smaller side fills the whole rectangle. Top and bottom edges are written
+    /// as row spans, left and right edges as short spans per row. `area` is in
+    /// logical coordinates and the outline is clipped to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Outer bounds of the rectangle
+    /// * `color` - Border color
+    /// * `thickness` - Border width in pixels; 0 draws nothing
+    pub fn draw_rect_outline(&mut self, area: &Rectangle, color: Color, thickness: u32) {
+        if thickness == 0 || area.is_zero_sized() {
+            return;
+        }
+
+        let w = area.size.width.min(i32::MAX as u32) as i32;
+        let h = area.size.height.min(i32::MAX as u32) as i32;
+        let t = thickness.min(i32::MAX as u32) as i32;
+        let (left, top) = (area.top_left.x, area.top_left.y);
+        let right = left.saturating_add(w - 1);
+        let bottom = top.saturating_add(h - 1);
+
+        let last_row = self.size().height as i32 - 1;
+        for y in top.max(0)..=bottom.min(last_row) {
+            if y - top < t || bottom - y < t {
+                self.logical_hline(left, right, y, color);
+            } else {
+                self.logical_hline(left, left.saturating_add(t - 1).min(right), y, color);
+                self.logical_hline(right.saturating_sub(t - 1).max(left), right, y, color);
+            }
+        }
+    }
+
+    /// Advances every pixel in the buffer to the next color (see [`Color::next`]).
+    ///
+    /// Unused nibble codes restart the cycle at `Black`.
+    pub fn cycle_buffer(&mut self) {
+        const fn next_nibble(nibble: u8) -> u8 {
+            match Color::from_nibble(nibble) {
+                Some(color) => color.next() as u8,
+                None => Color::Black as u8,
+            }
+        }
+
+        for byte in self.buffer.iter_mut() {
+            *byte = (next_nibble(*byte >> 4) << 4) | next_nibble(*byte & 0x0F);
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Draws a one-pixel line directly into the internal buffer.
+    ///
+    /// Uses Bresenham's algorithm, iterating along the major axis from the
+    /// lower endpoint, so swapping `start` and `end` plots the same pixels.
+    /// Endpoints are in logical coordinates and may lie anywhere in `i32`
+    /// range: the major axis is clipped to the display before stepping, so
+    /// only visible columns (or rows) are visited.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - First endpoint
+    /// * `end` - Second endpoint
+    /// * `color` - Line color
+    pub fn draw_line(&mut self, start: Point, end: Point, color: Color) {
+        // i64 holds every difference of two i32 coordinates
+        let (mut x0, mut y0) = (i64::from(start.x), i64::from(start.y));
+        let (mut x1, mut y1) = (i64::from(end.x), i64::from(end.y));
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            core::mem::swap(&mut x0, &mut y0);
+            core::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            core::mem::swap(&mut x0, &mut x1);
+            core::mem::swap(&mut y0, &mut y1);
+        }
+
+        let dx = x1 - x0;
+        let dy = (y1 - y0).abs();
+        let y_step = if y0 < y1 { 1 } else { -1 };
+
+        let size = self.size();
+        let major_len = i64::from(if steep { size.height } else { size.width });
+        let first = x0.max(0);
+        let last = x1.min(major_len - 1);
+        if first > last {
+            return;
+        }
+
+        // Advance the error term over the skipped columns in closed form:
+        // after k steps, err = dx / 2 - k * dy + m * dx for the m minor steps
+        // taken, the one value that keeps err within 0..dx
+        let skipped = i128::from(first - x0);
+        let behind = skipped * i128::from(dy) - i128::from(dx / 2);
+        let minor_steps = if behind > 0 {
+            (behind + i128::from(dx) - 1) / i128::from(dx)
+        } else {
+            0
+        };
+        let mut err = (minor_steps * i128::from(dx) - behind) as i64;
+        let mut y = y0 + minor_steps as i64 * y_step;
+
+        for x in first..=last {
+            let (px, py) = if steep { (y, x) } else { (x, y) };
+            if let (Ok(px), Ok(py)) = (i32::try_from(px), i32::try_from(py)) {
+                self.plot(px, py, color);
+            }
+            err -= dy;
+            if err < 0 {
+                y += y_step;
+                err += dx;
+            }
+        }
+    }
+
+    /// Draws an anti-aliased line as on/off pixels over the existing content.
+    ///
+    /// Computes Xiaolin Wu coverage for the two pixels straddling the line
+    /// at each step and plots `color` where the coverage exceeds
+    /// `threshold` at that pixel, so an ordered threshold renders partial
+    /// coverage as a stipple. Endpoints are whole pixels and are always
+    /// fully covered. Endpoints are in logical coordinates, as is the
+    /// threshold pattern, and may lie anywhere in `i32` range: the major
+    /// axis is clipped to the display before stepping.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - First endpoint
+    /// * `end` - Second endpoint
+    /// * `color` - Line color
+    /// * `threshold` - Coverage threshold per pixel
+    pub fn draw_line_aa(
+        &mut self,
+        start: Point,
+        end: Point,
+        color: Color,
+        threshold: CoverageThreshold,
+    ) {
+        // i64 holds the 16.16 positions of any i32 coordinates
+        let (mut x0, mut y0) = (i64::from(start.x), i64::from(start.y));
+        let (mut x1, mut y1) = (i64::from(end.x), i64::from(end.y));
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            core::mem::swap(&mut x0, &mut y0);
+            core::mem::swap(&mut x1, &mut y1);
+        }
+        if x0 > x1 {
+            core::mem::swap(&mut x0, &mut x1);
+            core::mem::swap(&mut y0, &mut y1);
+        }
+
+        let size = self.size();
+        let major_len = i64::from(if steep { size.height } else { size.width });
+        let first = x0.max(0);
+        let last = x1.min(major_len - 1);
+        if first > last {
+            return;
+        }
+
+        let dx = x1 - x0;
+        // Minor axis position in 16.16 fixed point, at the first visible step
+        let gradient = if dx == 0 { 0 } else { ((y1 - y0) << 16) / dx };
+        let mut intery = (y0 << 16) + (first - x0) * gradient;
+
+        for x in first..=last {
+            let y = intery >> 16;
+            let frac = ((intery & 0xFFFF) >> 8) as u8;
+            for (y, coverage) in [(y, 255 - frac), (y + 1, frac)] {
+                let (px, py) = if steep { (y, x) } else { (x, y) };
+                let (Ok(px), Ok(py)) = (u32::try_from(px), u32::try_from(py)) else {
+                    continue;
+                };
+                if coverage > threshold.at(px, py) {
+                    self.set_pixel(px, py, color);
+                }
+            }
+            intery += gradient;
+        }
+    }
+
+    /// Renders text in a `MonoFont` straight into the buffer.
+    ///
+    /// Each glyph row is split into runs of set and clear pixels, and runs
+    /// are written as spans, packing whole bytes, instead of going through
+    /// `draw_iter` pixel by pixel. `position` is the top-left corner of the
+    /// first glyph (embedded-graphics `Text` anchors at the baseline by
+    /// default). `bg` fills the glyph cells and the spacing between them;
+    /// `None` leaves those pixels untouched. `\n` starts a new line below
+    /// the first glyph. Pixels off the display are clipped; with a rotation
+    /// or mirroring set, pixels are written one by one in logical
+    /// coordinates. Returns the position for the glyph that would follow.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Characters to render; unknown ones use the font's replacement glyph
+    /// * `font` - Font to take glyphs and spacing from
+    /// * `position` - Top-left corner of the first glyph
+    /// * `fg` - Color of set glyph pixels
+    /// * `bg` - Color of clear glyph pixels, or `None` for transparent
+    #[cfg(feature = "alloc")]
+    pub fn draw_mono_text(
+        &mut self,
+        text: &str,
+        font: &MonoFont<'_>,
+        position: Point,
+        fg: Color,
+        bg: Option<Color>,
+    ) -> Point {
+        use embedded_graphics::image::ImageDrawable;
+
+        let Size { width, height } = font.character_size;
+        let glyphs_per_row = font.image.size().width / width.max(1);
+        let mut glyph = GlyphMask {
+            size: font.character_size,
+            on: vec![false; (width * height) as usize],
+        };
+        let mut cursor = position;
+        for c in text.chars() {
+            if c == '\n' {
+                cursor = Point::new(position.x, cursor.y + height as i32);
+                continue;
+            }
+            if glyphs_per_row == 0 {
+                break;
+            }
+            if cursor.x != position.x {
+                if let Some(bg) = bg {
+                    for y in 0..height as i32 {
+                        let x0 = cursor.x - font.character_spacing as i32;
+                        self.logical_hline(x0, cursor.x - 1, cursor.y + y, bg);
+                    }
+                }
+            }
+
+            let index = font.glyph_mapping.index(c) as u32;
+            let source = Point::new(
+                ((index % glyphs_per_row) * width) as i32,
+                ((index / glyphs_per_row) * height) as i32,
+            );
+            glyph.on.fill(false);
+            let _ = font
+                .image
+                .draw_sub_image(&mut glyph, &Rectangle::new(source, font.character_size));
+
+            for (y, row) in glyph.on.chunks_exact(width as usize).enumerate() {
+                let y = cursor.y + y as i32;
+                let mut start = 0;
+                while start < row.len() {
+                    let on = row[start];
+                    let len = row[start..].iter().take_while(|&&p| p == on).count();
+                    let color = if on { Some(fg) } else { bg };
+                    if let Some(color) = color {
+                        let x0 = cursor.x + start as i32;
+                        self.logical_hline(x0, x0 + len as i32 - 1, y, color);
+                    }
+                    start += len;
+                }
+            }
+            cursor.x += (width + font.character_spacing) as i32;
+        }
+        cursor
+    }
+
+    /// Fills the inclusive logical span `x0..=x1` of row `y`, clipped to the display.
+    fn logical_hline(&mut self, x0: i32, x1: i32, y: i32, color: Color) {
+        if self.is_untransformed() {
+            self.fill_hline(x0, x1, y, color);
+            return;
+        }
+        let size = self.size();
+        if y < 0 || y >= size.height as i32 {
+            return;
+        }
+        for x in x0.max(0)..=x1.min(size.width as i32 - 1) {
+            self.plot(x, y, color);
+        }
+    }
+
+    /// Moves a rectangular block of pixels within the buffer.
+    ///
+    /// Copies `src` so its top-left corner lands on `dst`. Overlapping source
+    /// and destination are handled like `memmove`: pixels are visited in an
+    /// order that reads each source pixel before it is overwritten. Works at
+    /// pixel (nibble) granularity, so odd offsets are fine. `src` and `dst`
+    /// are in logical coordinates. Source pixels outside the display are
+    /// skipped, as are destinations off-screen; the vacated area keeps its
+    /// old content.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - Block to copy
+    /// * `dst` - New top-left corner of the block
+    pub fn copy_region(&mut self, src: &Rectangle, dst: Point) {
+        let (w, h) = (src.size.width as i32, src.size.height as i32);
+        let offset = dst - src.top_left;
+        for j in 0..h {
+            let dy = if offset.y > 0 { h - 1 - j } else { j };
+            for i in 0..w {
+                let dx = if offset.x > 0 { w - 1 - i } else { i };
+                let from = src.top_left + Point::new(dx, dy);
+                let to = from + offset;
+                let Ok((x, y)) = <(u32, u32)>::try_from(from) else {
+                    continue;
+                };
+                if let Some(color) = self.get_pixel(x, y) {
+                    self.plot(to.x, to.y, color);
+                }
+            }
+        }
+    }
+
+    /// Raw nibble at `point`, or `None` outside the display.
+    fn nibble_at(&self, point: Point) -> Option<u8> {
+        let (x, y) = <(u32, u32)>::try_from(point).ok()?;
+        if x >= WIDTH || y >= HEIGHT {
+            return None;
+        }
+        let byte = self.buffer[((y * WIDTH + x) / 2) as usize];
+        Some(if x & 1 == 0 { byte >> 4 } else { byte & 0x0F })
+    }
+
+    /// Sets a pixel given signed coordinates, ignoring points outside the display.
+    fn plot(&mut self, x: i32, y: i32, color: Color) {
+        if x >= 0 && y >= 0 {
+            self.set_pixel(x as u32, y as u32, color);
+        }
+    }
+
+    /// Fills the inclusive span `x0..=x1` of row `y`, clipped to the display bounds.
+    fn fill_hline(&mut self, x0: i32, x1: i32, y: i32, color: Color) {
+        if y < 0 || y >= HEIGHT as i32 || x1 < 0 || x0 >= WIDTH as i32 {
+            return;
+        }
+        let x0 = x0.max(0) as u32;
+        let x1 = (x1.min(WIDTH as i32 - 1) + 1) as u32;
+        self.fill_span(x0, x1, y as u32, color);
+    }
+
+    /// Fills pixels `x0..x1` of row `y`, writing whole bytes where both nibbles are covered.
+    ///
+    /// Callers must clip the span to the display bounds.
+    fn fill_span(&mut self, x0: u32, x1: u32, y: u32, color: Color) {
+        if x0 >= x1 {
+            return;
+        }
+        self.grow_dirty(
+            Point::new(x0 as i32, y as i32),
+            Point::new(x1 as i32 - 1, y as i32),
+        );
+        fill_span(&mut *self.buffer, x0, x1, y, color);
+    }
+
+    fn reset(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.rst.set_low().map_err(Error::Pin)?;
+        self.delay.delay_ms(self.reset_delay_ms);
+        self.rst.set_high().map_err(Error::Pin)?;
+        self.delay.delay_ms(self.reset_delay_ms);
+        Ok(())
+    }
+
+    fn send_init_sequence(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        init_sequence(
+            self.cmdh,
+            self.booster_profile,
+            self.panel_setting,
+            self.cdi,
+            self.frame_rate,
+            |command, data| self.command_with_data(command, data),
+        )
+    }
+
+    fn write_command(&mut self, command: u8) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.dc.set_low().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = self.spi.write(&[command]).map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+        let result = self.spi.write(data).map_err(Error::Spi);
+        self.cs.set_high().map_err(Error::Pin)?;
+        result
+    }
+
+    fn command_with_data(
+        &mut self,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write_command(command)?;
+        self.write_data(data)
+    }
+
+    fn write_buffer_data(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write_buffer_data_with(|_, _| {})
+    }
+
+    /// Issues the data transmission command(s) preceding the buffer contents.
+    fn begin_frame(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write_command(CMD_DATA_START_TRANSMISSION)?;
+
+        #[cfg(feature = "split-transmission")]
+        if let Some(previous) = self.previous_frame.as_deref() {
+            self.dc.set_high().map_err(Error::Pin)?;
+            self.cs.set_low().map_err(Error::Pin)?;
+            let result = previous
+                .chunks(self.chunk_size)
+                .try_for_each(|chunk| write_mapped(&mut self.spi, self.color_map.as_ref(), chunk))
+                .map_err(Error::Spi);
+            self.cs.set_high().map_err(Error::Pin)?;
+            result?;
+            self.write_command(CMD_DATA_START_TRANSMISSION2)?;
+        }
+
+        Ok(())
+    }
+
+    /// Drops the `flush_delta` shadow once the panel shows something else.
+    fn invalidate_delta_shadow(&mut self) {
+        #[cfg(feature = "alloc")]
+        {
+            self.delta_shadow = None;
+        }
+    }
+
+    /// Records the buffer as the frame on the panel once it has been sent.
+    fn end_frame(&mut self) {
+        self.dirty = None;
+        #[cfg(feature = "alloc")]
+        if let Some(shadow) = self.delta_shadow.as_deref_mut() {
+            shadow.copy_from_slice(&self.buffer);
+        }
+        #[cfg(feature = "split-transmission")]
+        if let Some(mut previous) = self.previous_frame.take() {
+            self.copy_sent_frame(&mut previous);
+            self.previous_frame = Some(previous);
+        }
+    }
+
+    /// Copies the frame a buffer flush sends, with the overlay merged, into `frame`.
+    #[cfg(feature = "split-transmission")]
+    fn copy_sent_frame(&self, frame: &mut [u8]) {
+        frame.copy_from_slice(&self.buffer);
+        #[cfg(feature = "overlay")]
+        if let Some(overlay) = self.overlay.as_deref() {
+            for (byte, &top) in frame.iter_mut().zip(overlay.iter()) {
+                *byte = overlay_byte(*byte, top);
+            }
+        }
+    }
+
+    fn write_buffer_data_with<F>(&mut self, mut on_chunk: F) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        F: FnMut(usize, usize),
+    {
+        self.emit(FlushEvent::TransmitStart { bytes: BUFFER_SIZE });
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+
+        let mut result = Ok(());
+        let mut sent = 0;
+
+        for chunk in self.buffer.chunks(self.chunk_size) {
+            let map = self.color_map.as_ref();
+            #[cfg(feature = "overlay")]
+            let written = match self.overlay.as_deref() {
+                Some(overlay) => write_overlaid(
+                    &mut self.spi,
+                    map,
+                    &overlay[sent..sent + chunk.len()],
+                    chunk,
+                ),
+                None => write_mapped(&mut self.spi, map, chunk),
+            };
+            #[cfg(not(feature = "overlay"))]
+            let written = write_mapped(&mut self.spi, map, chunk);
+            if let Err(e) = written.map_err(Error::Spi) {
+                result = Err(e);
+                break;
+            }
+            sent += chunk.len();
+            on_chunk(sent, BUFFER_SIZE);
+        }
+
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes: sent });
+        Ok(())
+    }
+
+    fn write_packed_pixels<I>(&mut self, mut pixels: I) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        I: Iterator<Item = Color>,
+    {
+        self.invalidate_delta_shadow();
+        self.emit(FlushEvent::TransmitStart { bytes: BUFFER_SIZE });
+        self.dc.set_high().map_err(Error::Pin)?;
+        self.cs.set_low().map_err(Error::Pin)?;
+
+        let mut chunk = [0u8; STREAM_CHUNK_SIZE];
+        let mut remaining = BUFFER_SIZE;
+        let mut result = Ok(());
+
+        while remaining > 0 {
+            let len = remaining.min(STREAM_CHUNK_SIZE);
+            for byte in &mut chunk[..len] {
+                match (pixels.next(), pixels.next()) {
+                    (Some(hi), Some(lo)) => *byte = ((hi as u8) << 4) | lo as u8,
+                    _ => result = Err(Error::InvalidLength),
+                }
+            }
+            if result.is_err() {
+                break;
+            }
+            if let Err(e) = self.spi.write(&chunk[..len]).map_err(Error::Spi) {
+                result = Err(e);
+                break;
+            }
+            // The streamed frame is the old data of the next split flush
+            #[cfg(feature = "split-transmission")]
+            if let Some(previous) = self.previous_frame.as_deref_mut() {
+                let start = BUFFER_SIZE - remaining;
+                previous[start..start + len].copy_from_slice(&chunk[..len]);
+            }
+            remaining -= len;
+        }
+
+        self.cs.set_high().map_err(Error::Pin)?;
+        result?;
+        self.emit(FlushEvent::TransmitEnd { bytes: BUFFER_SIZE });
+        Ok(())
+    }
+
+    fn emit(&mut self, event: FlushEvent) {
+        match event {
+            FlushEvent::TransmitStart { .. } => self.pending_stats = FlushStats::default(),
+            FlushEvent::TransmitEnd { bytes } => self.pending_stats.bytes += bytes,
+            FlushEvent::RefreshEnd { busy_ms } => {
+                self.pending_stats.refresh_ms_est = busy_ms;
+                self.last_flush_stats = Some(self.pending_stats);
+            }
+            FlushEvent::RefreshStart | FlushEvent::Timeout { .. } => {}
+        }
+        #[cfg(feature = "alloc")]
+        if let Some(sink) = self.event_sink.as_mut() {
+            sink(event);
+        }
+    }
+
+    /// Waits for BUSY to clear, returning the approximate time waited in milliseconds.
+    fn wait_until_idle(&mut self) -> Result<u32, Error<SpiE, PinE, BusyE>> {
+        self.wait_until_idle_with(|| {})
+    }
+
+    /// Waits like `wait_until_idle`, calling `on_poll` after each poll step.
+    fn wait_until_idle_with<F>(&mut self, mut on_poll: F) -> Result<u32, Error<SpiE, PinE, BusyE>>
+    where
+        F: FnMut(),
+    {
+        self.busy_polls = 0;
+        let delay = &mut self.delay;
+        #[cfg(feature = "alloc")]
+        let waiter = &mut self.busy_waiter;
+        let result = poll_until_idle(
+            &mut self.busy,
+            self.busy_active_high,
+            self.busy_timeout_ms,
+            &mut self.busy_polls,
+            |max_ms| {
+                #[cfg(feature = "alloc")]
+                let slept = match waiter.as_mut() {
+                    Some(waiter) => waiter(max_ms)?,
+                    None => delay_step(delay, max_ms),
+                };
+                #[cfg(not(feature = "alloc"))]
+                let slept = delay_step(delay, max_ms);
+                on_poll();
+                Ok(slept)
+            },
+        );
+        match result {
+            Ok(waited_ms) => Ok(waited_ms),
+            Err(BusyWaitError::Pin(e)) => Err(Error::Busy(e)),
+            Err(BusyWaitError::Timeout(waited_ms)) => Err(self.busy_timeout(waited_ms)),
+            Err(BusyWaitError::Aborted) => Err(Error::WaiterFailed),
+        }
+    }
+
+    /// Polls until BUSY asserts, failing after `timeout_ms`.
+    fn wait_until_busy(&mut self, timeout_ms: u32) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        poll_until_busy(
+            &mut self.busy,
+            &mut self.delay,
+            self.busy_active_high,
+            timeout_ms,
+        )
+    }
+
+    fn busy_timeout(&mut self, waited_ms: u32) -> Error<SpiE, PinE, BusyE> {
+        self.emit(FlushEvent::Timeout { waited_ms });
+        Error::Timeout
+    }
+
+    fn power_on(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.write_command(CMD_POWER_ON)?;
+        let result = self.wait_until_idle();
+        self.init_busy_polls = Some(self.busy_polls);
+        result?;
+        Ok(())
+    }
+
+    fn power_off(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.command_with_data(CMD_POWER_OFF, &[0x00])?;
+        self.wait_until_idle()?;
+        Ok(())
+    }
+
+    /// Refreshes the panel with the frame last sent by [`upload`](Self::upload).
+    ///
+    /// Blocks until BUSY is released, which may take several seconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn refresh(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.refresh_with(|| {})
+    }
+
+    fn refresh_with<F>(&mut self, on_poll: F) -> Result<(), Error<SpiE, PinE, BusyE>>
+    where
+        F: FnMut(),
+    {
+        self.emit(FlushEvent::RefreshStart);
+        self.start_refresh()?;
+        if let Some(timeout_ms) = self.busy_ack_timeout_ms {
+            self.wait_until_busy(timeout_ms)?;
+        }
+        let busy_ms = self.wait_until_idle_with(on_poll)?;
+        self.emit(FlushEvent::RefreshEnd { busy_ms });
+        Ok(())
+    }
+
+    fn start_refresh(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if self.pre_refresh_delay_ms > 0 {
+            self.delay.delay_ms(self.pre_refresh_delay_ms);
+        }
+        self.command_with_data(CMD_DISPLAY_REFRESH, &[self.refresh_param])
+    }
+}
+
+impl<BUS, CS, DC, RST, BUSY, DELAY, PinE, BusyE>
+    Gdep073e01<'_, bus::ManualCsBus<BUS>, CS, DC, RST, BUSY, DELAY>
+where
+    BUS: SpiBus<u8>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    /// Creates a driver on an exclusively owned `SpiBus`, for HALs without `SpiDevice`.
+    ///
+    /// The driver asserts `cs` around every transfer itself, as it does with
+    /// an `SpiDevice`; see [`ManualCsBus`](bus::ManualCsBus).
+    ///
+    /// # Arguments
+    ///
+    /// * `bus` - SPI bus, not shared with other devices
+    /// * `cs` - Chip select pin (active low)
+    /// * `dc` - Data/command selection pin
+    /// * `rst` - Reset pin (active low)
+    /// * `busy` - Busy status pin
+    /// * `delay` - Delay provider
+    #[cfg(feature = "alloc")]
+    pub fn new_with_bus(bus: BUS, cs: CS, dc: DC, rst: RST, busy: BUSY, delay: DELAY) -> Self {
+        Self::new(bus::ManualCsBus::new(bus), cs, dc, rst, busy, delay)
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> Drop for Gdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY> {
+    /// Runs `shutdown()` if enabled with `set_sleep_on_drop`, ignoring errors.
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.sleep_on_drop.take() {
+            shutdown(self);
+        }
+    }
+}
+
+/// Frame buffer storage, owned on the heap or borrowed from the caller.
+enum FrameBuffer<'a> {
+    #[cfg(feature = "alloc")]
+    Owned(Box<[u8]>),
+    Borrowed(&'a mut [u8]),
+}
+
+#[cfg(feature = "alloc")]
+impl From<Box<[u8]>> for FrameBuffer<'_> {
+    fn from(buffer: Box<[u8]>) -> Self {
+        FrameBuffer::Owned(buffer)
+    }
+}
+
+impl core::ops::Deref for FrameBuffer<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(feature = "alloc")]
+            FrameBuffer::Owned(buffer) => buffer,
+            FrameBuffer::Borrowed(buffer) => buffer,
+        }
+    }
+}
+
+impl core::ops::DerefMut for FrameBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            #[cfg(feature = "alloc")]
+            FrameBuffer::Owned(buffer) => buffer,
+            FrameBuffer::Borrowed(buffer) => buffer,
+        }
+    }
+}
+
+/// Allocates a white buffer of `len` bytes without panicking on failure.
+#[cfg(feature = "alloc")]
+fn try_alloc_buffer(len: usize) -> Result<Box<[u8]>, TryReserveError> {
+    let mut buffer = Vec::new();
+    buffer.try_reserve_exact(len)?;
+    buffer.resize(len, 0x11);
+    Ok(buffer.into_boxed_slice())
+}
+
+/// SPI device that records writes as data, for `flush_bytes`.
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+struct WireRecorder {
+    writes: Vec<(bool, Vec<u8>)>,
+}
+
+#[cfg(feature = "alloc")]
+impl WireRecorder {
+    fn command(&mut self, command: u8) {
+        self.writes.push((true, vec![command]));
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl embedded_hal::spi::ErrorType for WireRecorder {
+    type Error = core::convert::Infallible;
+}
+
+#[cfg(feature = "alloc")]
+impl SpiDevice<u8> for WireRecorder {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        for op in operations {
+            if let Operation::Write(data) = op {
+                self.writes.push((false, data.to_vec()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One font glyph unpacked to a flag per pixel, row-major.
+#[cfg(feature = "alloc")]
+struct GlyphMask {
+    size: Size,
+    on: Vec<bool>,
+}
+
+#[cfg(feature = "alloc")]
+impl OriginDimensions for GlyphMask {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DrawTarget for GlyphMask {
+    type Color = BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Ok((x, y)) = <(u32, u32)>::try_from(point) {
+                if x < self.size.width && y < self.size.height {
+                    self.on[(y * self.size.width + x) as usize] = color.is_on();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Borrowed view of a buffer region, formatted as a color grid.
+struct BufferRegion<'a> {
+    buffer: &'a [u8],
+    area: Rectangle,
+}
+
+impl core::fmt::Debug for BufferRegion<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        let Point { x: x0, y: y0 } = self.area.top_left;
+        for dy in 0..self.area.size.height {
+            if dy > 0 {
+                f.write_char('\n')?;
+            }
+            for dx in 0..self.area.size.width {
+                let (x, y) = (x0 as u32 + dx, y0 as u32 + dy);
+                let byte = self.buffer[((y * WIDTH + x) / 2) as usize];
+                let nibble = if x & 1 == 0 { byte >> 4 } else { byte & 0x0F };
+                f.write_char(match Color::from_nibble(nibble) {
+                    Some(Color::Black) => 'K',
+                    Some(Color::White) => 'W',
+                    Some(Color::Yellow) => 'Y',
+                    Some(Color::Red) => 'R',
+                    Some(Color::Orange) => 'O',
+                    Some(Color::Blue) => 'B',
+                    Some(Color::Green) => 'G',
+                    None => '?',
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Half-width of the midpoint circle of radius `r` in the row `d` away from the center.
+///
+/// The midpoint algorithm's x at row y is the largest x with
+/// `x * (x - 1) < r² - y²`. Rows within the first octant take that x;
+/// rows beyond it are reached by the mirrored octant, whose width is the
+/// last y with x still at least `d`. Requires `d <= r`.
+fn circle_half_width(r: u64, d: u64) -> u64 {
+    if r == 0 {
+        return 0;
+    }
+    let t = r * r - d * d;
+    if t > 0 {
+        let mut x = t.isqrt() + 1;
+        while x * (x - 1) >= t {
+            x -= 1;
+        }
+        if x >= d {
+            return x;
+        }
+    }
+    (t + d - 1).isqrt()
+}
+
+/// Returns `byte` with the nibble of column `x` set to `color`.
+///
+/// Even columns take the high nibble, odd columns the low one.
+#[allow(clippy::manual_is_multiple_of)]
+const fn pack_nibble(byte: u8, x: u32, color: Color) -> u8 {
+    if x % 2 == 0 {
+        (byte & 0x0F) | ((color as u8) << 4)
+    } else {
+        (byte & 0xF0) | color as u8
+    }
+}
+
+/// Why [`poll_until_idle`] stopped waiting.
+enum BusyWaitError<E> {
+    /// Reading BUSY failed.
+    Pin(E),
+    /// BUSY stayed asserted; carries the time waited in milliseconds.
+    Timeout(u32),
+    /// `sleep` failed.
+    Aborted,
+}
+
+/// Polls BUSY until idle, returning the approximate time waited in milliseconds.
+///
+/// Each poll step calls `sleep` with the time left before `timeout_ms`
+/// (`u32::MAX` for a timeout of 0, which waits forever); it returns the
+/// milliseconds it actually slept, which count against the timeout. A
+/// failing `sleep` aborts the wait. `polls` counts the steps.
+fn poll_until_idle<BUSY, F>(
+    busy: &mut BUSY,
+    active_high: bool,
+    timeout_ms: u32,
+    polls: &mut u32,
+    mut sleep: F,
+) -> Result<u32, BusyWaitError<BUSY::Error>>
+where
+    BUSY: InputPin,
+    F: FnMut(u32) -> Result<u32, ()>,
+{
+    let mut waited_ms = 0u32;
+    while busy.is_high().map_err(BusyWaitError::Pin)? == active_high {
+        *polls += 1;
+        let max_ms = match timeout_ms {
+            0 => u32::MAX,
+            _ if waited_ms >= timeout_ms => return Err(BusyWaitError::Timeout(waited_ms)),
+            _ => timeout_ms - waited_ms,
+        };
+        let slept = sleep(max_ms).map_err(|()| BusyWaitError::Aborted)?;
+        waited_ms = waited_ms.saturating_add(slept);
+    }
+    Ok(waited_ms)
+}
+
+/// Sleeps one delay-based poll step of at most `max_ms`, returning its length.
+fn delay_step<DELAY: DelayNs>(delay: &mut DELAY, max_ms: u32) -> u32 {
+    let step = max_ms.min(BUSY_WAIT_DELAY_MS);
+    delay.delay_ms(step);
+    step
+}
+
+/// Polls every `BUSY_ACK_POLL_MS` until BUSY asserts, failing after `timeout_ms`.
+fn poll_until_busy<BUSY, DELAY, SpiE, PinE>(
+    busy: &mut BUSY,
+    delay: &mut DELAY,
+    active_high: bool,
+    timeout_ms: u32,
+) -> Result<(), Error<SpiE, PinE, BUSY::Error>>
+where
+    BUSY: InputPin,
+    DELAY: DelayNs,
+{
+    let mut waited = 0;
+    while busy.is_high().map_err(Error::Busy)? != active_high {
+        if waited >= timeout_ms {
+            return Err(Error::BusyNeverAsserted);
+        }
+        delay.delay_ms(BUSY_ACK_POLL_MS);
+        waited += BUSY_ACK_POLL_MS;
+    }
+    Ok(())
+}
+
+/// Fills pixels `x0..x1` of row `y` in a packed frame buffer.
+///
+/// A ragged edge costs one read and one write; every byte between them is
+/// written once without being read. The span must be non-empty and on-screen.
+fn fill_span<B>(buffer: &mut B, x0: u32, x1: u32, y: u32, color: Color)
+where
+    B: IndexMut<usize, Output = u8> + ?Sized,
+{
+    let row = (y * WIDTH) as usize / 2;
+    let mut x0 = x0;
+    let mut x1 = x1;
+
+    // Ragged left edge: odd start only covers the low nibble
+    if x0 & 1 == 1 {
+        let index = row + x0 as usize / 2;
+        buffer[index] = pack_nibble(buffer[index], x0, color);
+        x0 += 1;
+    }
+    // Ragged right edge: odd end only covers the high nibble
+    if x1 & 1 == 1 && x1 > x0 {
+        let index = row + x1 as usize / 2;
+        buffer[index] = pack_nibble(buffer[index], x1 - 1, color);
+        x1 -= 1;
+    }
+    let packed_color = (color as u8) * 0x11;
+    for index in row + x0 as usize / 2..row + x1 as usize / 2 {
+        buffer[index] = packed_color;
+    }
+}
+
+/// Writes pixels into a packed frame buffer, skipping those off-screen.
+///
+/// The byte under edit is kept in a register until a pixel lands in another
+/// byte, so row-ordered runs such as rendered text cost one buffer read and
+/// one write per byte instead of per pixel. Returns the inclusive corners
+/// of the pixels written, if any.
+fn write_pixels<B, I>(buffer: &mut B, pixels: I) -> Option<(Point, Point)>
+where
+    B: IndexMut<usize, Output = u8> + ?Sized,
+    I: IntoIterator<Item = Pixel<Color>>,
+{
+    let mut pending: Option<(usize, u8)> = None;
+    let mut bounds: Option<(Point, Point)> = None;
+    for Pixel(coord, color) in pixels {
+        let Ok((x, y)) = <(u32, u32)>::try_from(coord) else {
+            continue;
+        };
+        if x >= WIDTH || y >= HEIGHT {
+            continue;
+        }
+        bounds = Some(match bounds {
+            Some((a, b)) => (a.component_min(coord), b.component_max(coord)),
+            None => (coord, coord),
+        });
+        let index = ((y * WIDTH + x) / 2) as usize;
+        let byte = match pending {
+            Some((i, byte)) if i == index => byte,
+            _ => {
+                if let Some((i, byte)) = pending {
+                    buffer[i] = byte;
+                }
+                buffer[index]
+            }
+        };
+        pending = Some((index, pack_nibble(byte, x, color)));
+    }
+    if let Some((i, byte)) = pending {
+        buffer[i] = byte;
+    }
+    bounds
+}
+
+/// Issues the panel initialization commands through `command`.
+fn init_sequence<E>(
+    cmdh: [u8; 6],
+    booster: BoosterProfile,
+    panel_setting: [u8; 2],
+    cdi: u8,
+    frame_rate: FrameRate,
+    mut command: impl FnMut(u8, &[u8]) -> Result<(), E>,
+) -> Result<(), E> {
+    command(CMD_CMDH, &cmdh)?;
+    command(CMD_POWER_SETTING, &[0x3F])?;
+    command(CMD_PANEL_SETTING, &panel_setting)?;
+    command(CMD_POFS, &[0x00, 0x54, 0x00, 0x44])?;
+    let [btst1, btst2, btst3] = booster.booster_bytes();
+    command(CMD_BOOSTER_SOFT_START1, &btst1)?;
+    command(CMD_BOOSTER_SOFT_START2, &btst2)?;
+    command(CMD_BOOSTER_SOFT_START3, &btst3)?;
+    command(CMD_PLL_CONTROL, &[frame_rate.pll_byte()])?;
+    command(CMD_CDI, &[cdi])?;
+    command(CMD_TCON_SETTING, &[0x02, 0x00])?;
+    command(CMD_TRES, &TRES_DATA)?;
+    command(CMD_T_VDCS, &[0x01])?;
+    command(CMD_PWS, &[0x2F])
+}
+
+/// `CMD_CDI` payload with the border (VBD) driven to `color`.
+const fn cdi_for_border(color: Color) -> u8 {
+    (CDI_DATA & !(0x07 << CDI_BORDER_SHIFT)) | ((color as u8) << CDI_BORDER_SHIFT)
+}
+
+/// Writes frame `data` like `write_mapped`, with `overlay` merged over it.
+///
+/// `overlay` covers the same pixels as `data`.
+#[cfg(feature = "overlay")]
+fn write_overlaid<SPI: SpiDevice<u8>>(
+    spi: &mut SPI,
+    map: Option<&[u8; 256]>,
+    overlay: &[u8],
+    data: &[u8],
+) -> Result<(), SPI::Error> {
+    let mut staged = [0u8; STREAM_CHUNK_SIZE];
+    for (part, over) in data
+        .chunks(STREAM_CHUNK_SIZE)
+        .zip(overlay.chunks(STREAM_CHUNK_SIZE))
+    {
+        for ((out, &base), &top) in staged.iter_mut().zip(part).zip(over) {
+            *out = overlay_byte(base, top);
+        }
+        write_mapped(spi, map, &staged[..part.len()])?;
+    }
+    Ok(())
+}
+
+/// Two packed pixels of `top` drawn over `base`; transparent nibbles keep `base`.
+#[cfg(feature = "overlay")]
+const fn overlay_byte(base: u8, top: u8) -> u8 {
+    let hi = if top >> 4 == OVERLAY_TRANSPARENT {
+        base
+    } else {
+        top
+    };
+    let lo = if top & 0x0F == OVERLAY_TRANSPARENT {
+        base
+    } else {
+        top
+    };
+    (hi & 0xF0) | (lo & 0x0F)
+}
+
+/// Writes `data`, translated through `map` if one is set.
+///
+/// Mapped data is staged through a small stack buffer, so it goes out in
+/// `STREAM_CHUNK_SIZE` pieces.
+fn write_mapped<SPI: SpiDevice<u8>>(
+    spi: &mut SPI,
+    map: Option<&[u8; 256]>,
+    data: &[u8],
+) -> Result<(), SPI::Error> {
+    let Some(map) = map else {
+        return spi.write(data);
+    };
+    let mut staged = [0u8; STREAM_CHUNK_SIZE];
+    for part in data.chunks(STREAM_CHUNK_SIZE) {
+        for (out, &byte) in staged.iter_mut().zip(part) {
+            *out = map[byte as usize];
+        }
+        spi.write(&staged[..part.len()])?;
+    }
+    Ok(())
+}
+
+/// Checks that a `CMD_TRES` payload describes `WIDTH`x`HEIGHT` and that a
+/// buffer of `buffer_len` bytes holds exactly one such frame.
+const fn resolution_matches(tres: [u8; 4], buffer_len: usize) -> bool {
+    let width = u16::from_be_bytes([tres[0], tres[1]]) as u32;
+    let height = u16::from_be_bytes([tres[2], tres[3]]) as u32;
+    width == WIDTH && height == HEIGHT && buffer_len == (width * height / 2) as usize
+}
+
+/// Estimated time in milliseconds to clock `bytes` out at `clock_hz`.
+fn transfer_time_ms(bytes: usize, clock_hz: u32) -> u32 {
+    if clock_hz == 0 {
+        return 0;
+    }
+    (bytes as u64 * 8 * 1000).div_ceil(clock_hz as u64) as u32
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE> DrawTarget
+    for Gdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    type Color = Color;
+    type Error = Error<SpiE, PinE, BusyE>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        if !self.is_untransformed() {
+            for Pixel(point, color) in pixels {
+                if point.x >= 0 && point.y >= 0 {
+                    self.set_pixel(point.x as u32, point.y as u32, color);
+                }
+            }
+            return Ok(());
+        }
+        if let Some((top_left, bottom_right)) = write_pixels(&mut self.buffer[..], pixels) {
+            self.grow_dirty(top_left, bottom_right);
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+        // A rotated or mirrored rectangle is still a rectangle in the physical layout
+        let corner = |p: Point| {
+            let (x, y) = self.to_physical(p.x as u32, p.y as u32).unwrap_or_default();
+            Point::new(x as i32, y as i32)
+        };
+        let area = Rectangle::with_corners(corner(area.top_left), corner(bottom_right));
+
         let start_x = area.top_left.x as u32;
         let start_y = area.top_left.y as u32;
         let end_x = (area.top_left.x + area.size.width as i32) as u32;
         let end_y = (area.top_left.y + area.size.height as i32) as u32;
 
-        for y in start_y..end_y {
-            for x in start_x..end_x {
-                self.set_pixel(x, y, color);
+        for y in start_y..end_y {
+            self.fill_span(start_x, end_x, y, color);
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let drawable = area.intersection(&self.bounding_box());
+        if drawable.is_zero_sized() {
+            return Ok(());
+        }
+        if !self.is_untransformed() {
+            for (point, color) in area.points().zip(colors) {
+                if point.x >= 0 && point.y >= 0 {
+                    self.set_pixel(point.x as u32, point.y as u32, color);
+                }
+            }
+            return Ok(());
+        }
+
+        // Colors cover the whole `area` row by row; skip the clipped parts
+        let area_width = area.size.width as usize;
+        let visible = drawable.size.width as usize;
+        let skip_left = (drawable.top_left.x - area.top_left.x) as usize;
+        let skip_right = area_width - visible - skip_left;
+        let skip_top = (drawable.top_left.y - area.top_left.y) as usize * area_width;
+
+        let mut colors = colors.into_iter();
+        if skip_top > 0 {
+            colors.nth(skip_top - 1);
+        }
+
+        let x0 = drawable.top_left.x as u32;
+        let x1 = x0 + drawable.size.width;
+        let y0 = drawable.top_left.y as u32;
+        if let Some(bottom_right) = drawable.bottom_right() {
+            self.grow_dirty(drawable.top_left, bottom_right);
+        }
+
+        for y in y0..y0 + drawable.size.height {
+            if skip_left > 0 {
+                colors.nth(skip_left - 1);
+            }
+
+            let row = (y * WIDTH) as usize / 2;
+            let mut x = x0;
+            while x < x1 {
+                let Some(first) = colors.next() else {
+                    return Ok(());
+                };
+                let index = row + x as usize / 2;
+                if x & 1 == 1 {
+                    self.buffer[index] = (self.buffer[index] & 0xF0) | first as u8;
+                    x += 1;
+                } else if x + 1 < x1 {
+                    let Some(second) = colors.next() else {
+                        self.set_pixel(x, y, first);
+                        return Ok(());
+                    };
+                    self.buffer[index] = ((first as u8) << 4) | second as u8;
+                    x += 2;
+                } else {
+                    self.buffer[index] = (self.buffer[index] & 0x0F) | ((first as u8) << 4);
+                    x += 1;
+                }
+            }
+
+            if skip_right > 0 {
+                colors.nth(skip_right - 1);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_buffer(color);
+        Ok(())
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> OriginDimensions
+    for Gdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY>
+{
+    fn size(&self) -> Size {
+        if self.rotation.is_portrait() {
+            Size::new(HEIGHT, WIDTH)
+        } else {
+            Size::new(WIDTH, HEIGHT)
+        }
+    }
+}
+
+/// Prelude module for convenient imports.
+pub mod prelude {
+    #[cfg(feature = "alloc")]
+    pub use crate::Gdep073e01Builder;
+    pub use crate::{
+        BoardPreset, BoosterProfile, Color, CoverageThreshold, Error, FlushEvent, FlushProgress,
+        FlushStats, FrameRate, Gdep073e01, InitProgress, Rotation, HEIGHT, REFRESH_FULL, WIDTH,
+    };
+    pub use embedded_graphics::prelude::*;
+    pub use embedded_hal::{
+        delay::DelayNs,
+        digital::{ErrorType as DigitalErrorType, InputPin, OutputPin},
+        spi::{ErrorType as SpiErrorType, SpiDevice},
+    };
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use embedded_hal::digital::{ErrorType as DigitalErrorType, PinState};
+    use embedded_hal::spi::{ErrorType as SpiErrorType, Operation};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct MockError;
+
+    impl embedded_hal::digital::Error for MockError {
+        fn kind(&self) -> embedded_hal::digital::ErrorKind {
+            embedded_hal::digital::ErrorKind::Other
+        }
+    }
+
+    impl embedded_hal::spi::Error for MockError {
+        fn kind(&self) -> embedded_hal::spi::ErrorKind {
+            embedded_hal::spi::ErrorKind::Other
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockSpi {
+        pub writes: Vec<Vec<u8>>,
+        /// Byte returned for every read
+        pub read_value: u8,
+    }
+
+    impl SpiErrorType for MockSpi {
+        type Error = MockError;
+    }
+
+    impl SpiDevice<u8> for MockSpi {
+        fn transaction(&mut self, operations: &mut [Operation<u8>]) -> Result<(), Self::Error> {
+            for op in operations {
+                match op {
+                    Operation::Write(data) => self.writes.push(data.to_vec()),
+                    Operation::Read(buf) => buf.fill(self.read_value),
+                    _ => {}
+                }
+            }
+            Ok(())
+        }
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.writes.push(words.to_vec());
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockPin {
+        pub states: Vec<PinState>,
+        /// Number of upcoming `is_high` reads that return true
+        pub high_reads: usize,
+    }
+
+    impl DigitalErrorType for MockPin {
+        type Error = MockError;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.states.push(PinState::Low);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.states.push(PinState::High);
+            Ok(())
+        }
+    }
+
+    impl InputPin for MockPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            // Not busy for tests unless reads were queued
+            if self.high_reads > 0 {
+                self.high_reads -= 1;
+                return Ok(true);
+            }
+            Ok(false)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            self.is_high().map(|high| !high)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockDelay {
+        pub delays_ns: Vec<u32>,
+    }
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.delays_ns.push(ns);
+        }
+    }
+
+    #[test]
+    fn test_set_pixel() {
+        let spi = MockSpi::default();
+        let cs = MockPin::default();
+        let dc = MockPin::default();
+        let rst = MockPin::default();
+        let busy = MockPin::default();
+        let delay = MockDelay::default();
+
+        let mut display = Gdep073e01::new(spi, cs, dc, rst, busy, delay);
+
+        display.set_pixel(0, 0, Color::Black);
+        assert_eq!(display.buffer[0], 0x01);
+
+        display.set_pixel(1, 0, Color::Red);
+        assert_eq!(display.buffer[0], 0x03);
+    }
+
+    #[test]
+    fn test_clear_buffer() {
+        let spi = MockSpi::default();
+        let cs = MockPin::default();
+        let dc = MockPin::default();
+        let rst = MockPin::default();
+        let busy = MockPin::default();
+        let delay = MockDelay::default();
+
+        let mut display = Gdep073e01::new(spi, cs, dc, rst, busy, delay);
+
+        display.clear_buffer(Color::Orange);
+        assert!(display.buffer.iter().all(|&byte| byte == 0x44));
+    }
+
+    #[test]
+    fn test_color_iter() {
+        let colors: Vec<Color> = Color::iter().collect();
+        assert_eq!(colors.len(), Color::COUNT);
+        assert!(colors.iter().enumerate().all(|(i, &c)| c as usize == i));
+    }
+
+    #[test]
+    fn test_load_3bpp() {
+        let mut display = new_display();
+        for y in 0..HEIGHT {
+            for x in (y % 5..WIDTH).step_by(7) {
+                display.set_pixel(x, y, Color::ALL[((x + y) % 7) as usize]);
+            }
+        }
+        let pixels: Vec<Color> = (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .map(|(x, y)| display.get_pixel(x, y).unwrap())
+            .collect();
+        let packed = codec::pack_3bpp(&pixels);
+        assert_eq!(packed.len(), BUFFER_SIZE * 3 / 4);
+
+        let mut loaded = new_display();
+        loaded.load_3bpp(&packed).unwrap();
+        assert_eq!(*loaded.buffer, *display.buffer);
+        assert!(loaded.take_dirty().is_some());
+
+        assert!(matches!(
+            loaded.load_3bpp(&packed[1..]),
+            Err(Error::InvalidLength)
+        ));
+        assert_eq!(
+            Color::from_bits3(Color::Orange.to_bits3()),
+            Some(Color::Orange)
+        );
+        assert_eq!(Color::from_bits3(7), None);
+    }
+
+    #[test]
+    fn test_get_pixel() {
+        let mut display = new_display();
+        for (i, &color) in Color::ALL.iter().enumerate() {
+            display.set_pixel(i as u32, 3, color);
+            assert_eq!(display.get_pixel(i as u32, 3), Some(color));
+        }
+        assert_eq!(display.get_pixel(WIDTH - 1, HEIGHT - 1), Some(Color::White));
+        assert_eq!(display.get_pixel(WIDTH, 0), None);
+        assert_eq!(display.get_pixel(0, HEIGHT), None);
+
+        // Unused codes decode to None
+        display.buffer[0] = 0x7F;
+        assert_eq!(display.get_pixel(0, 0), None);
+        assert_eq!(display.get_pixel(1, 0), None);
+
+        // Logical coordinates follow the rotation
+        display.set_rotation(Rotation::Deg90);
+        display.set_pixel(10, 20, Color::Green);
+        assert_eq!(display.get_pixel(10, 20), Some(Color::Green));
+        assert_eq!(display.get_pixel(HEIGHT - 1, 0), Some(Color::White));
+        assert_eq!(display.get_pixel(HEIGHT, 0), None);
+    }
+
+    #[test]
+    fn test_region_checksum() {
+        let mut display = new_display();
+        let area = Rectangle::new(Point::new(10, 10), Size::new(20, 5));
+        let before = display.region_checksum(&area);
+
+        // Changes outside the region leave it unchanged
+        display.set_pixel(9, 10, Color::Red);
+        display.set_pixel(30, 14, Color::Red);
+        display.set_pixel(15, 15, Color::Red);
+        assert_eq!(display.region_checksum(&area), before);
+
+        display.set_pixel(29, 14, Color::Red);
+        let after = display.region_checksum(&area);
+        assert_ne!(after, before);
+        display.set_pixel(29, 14, Color::Blue);
+        assert_ne!(display.region_checksum(&area), after);
+
+        // Clipped to the display
+        let corner = Rectangle::new(Point::new(WIDTH as i32 - 4, -3), Size::new(10, 6));
+        let clipped = Rectangle::new(Point::new(WIDTH as i32 - 4, 0), Size::new(4, 3));
+        assert_eq!(
+            display.region_checksum(&corner),
+            display.region_checksum(&clipped)
+        );
+    }
+
+    #[test]
+    fn test_flush_bytes() {
+        let mut display = new_display();
+        display.set_pixel(3, 2, Color::Green);
+        display.set_pixel(WIDTH - 1, HEIGHT - 1, Color::Red);
+        display.set_refresh_param(0x01);
+
+        let expected = display.flush_bytes();
+        assert_eq!(expected[0], (true, vec![CMD_DATA_START_TRANSMISSION]));
+        assert_eq!(
+            expected[expected.len() - 2],
+            (true, vec![CMD_DISPLAY_REFRESH])
+        );
+        assert_eq!(expected[expected.len() - 1], (false, vec![0x01]));
+        assert_eq!(expected.iter().filter(|(command, _)| *command).count(), 2);
+
+        display.flush().unwrap();
+        let bytes: Vec<Vec<u8>> = expected.into_iter().map(|(_, bytes)| bytes).collect();
+        assert_eq!(display.spi.writes, bytes);
+
+        // Color maps split the data into smaller writes
+        let mut map = Color::ALL;
+        map[Color::Green as usize] = Color::Blue;
+        display.set_color_map(map);
+        display.spi.writes.clear();
+        let expected = display.flush_bytes();
+        display.flush().unwrap();
+        let bytes: Vec<Vec<u8>> = expected.into_iter().map(|(_, bytes)| bytes).collect();
+        assert_eq!(display.spi.writes, bytes);
+    }
+
+    #[test]
+    fn test_load_buffer() {
+        let mut display = new_display();
+        let frame: Vec<u8> = (0..BUFFER_SIZE).map(|i| (i % 7) as u8 * 0x11).collect();
+        display.load_buffer(&frame).unwrap();
+        assert_eq!(*display.buffer, frame[..]);
+        assert_eq!(nibble(&display, 4, 0), Color::Yellow as u8);
+
+        // Wrong sizes are rejected without touching the buffer
+        for len in [0, BUFFER_SIZE - 1, BUFFER_SIZE + 1] {
+            assert!(matches!(
+                display.load_buffer(&vec![0x00; len]),
+                Err(Error::InvalidLength)
+            ));
+        }
+        assert_eq!(*display.buffer, frame[..]);
+        assert!(display.spi.writes.is_empty());
+    }
+
+    /// Frame buffer wrapper counting element reads and writes.
+    struct Counting {
+        bytes: Vec<u8>,
+        reads: core::cell::Cell<usize>,
+        writes: usize,
+    }
+
+    impl Counting {
+        fn new() -> Self {
+            Counting {
+                bytes: vec![0x11; BUFFER_SIZE],
+                reads: core::cell::Cell::new(0),
+                writes: 0,
+            }
+        }
+    }
+
+    impl core::ops::Index<usize> for Counting {
+        type Output = u8;
+        fn index(&self, i: usize) -> &u8 {
+            self.reads.set(self.reads.get() + 1);
+            &self.bytes[i]
+        }
+    }
+
+    impl IndexMut<usize> for Counting {
+        fn index_mut(&mut self, i: usize) -> &mut u8 {
+            self.writes += 1;
+            &mut self.bytes[i]
+        }
+    }
+
+    fn new_display() -> Gdep073e01<'static, MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay>
+    {
+        Gdep073e01::new(
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockDelay::default(),
+        )
+    }
+
+    /// Concatenates the data bytes sent after the last data start transmission command.
+    fn transmitted_frame(writes: &[Vec<u8>]) -> Vec<u8> {
+        let start = writes
+            .iter()
+            .rposition(|w| w.as_slice() == [CMD_DATA_START_TRANSMISSION])
+            .expect("no data transmission");
+        writes[start + 1..]
+            .iter()
+            .take_while(|w| w.as_slice() != [CMD_DISPLAY_REFRESH])
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// BUSY input from a different HAL, with its own error type.
+    struct FaultyBusy;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct BusyFault;
+
+    impl embedded_hal::digital::Error for BusyFault {
+        fn kind(&self) -> embedded_hal::digital::ErrorKind {
+            embedded_hal::digital::ErrorKind::Other
+        }
+    }
+
+    impl DigitalErrorType for FaultyBusy {
+        type Error = BusyFault;
+    }
+
+    impl InputPin for FaultyBusy {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Err(BusyFault)
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Err(BusyFault)
+        }
+    }
+
+    #[test]
+    fn test_read_temperature() {
+        let mut display = new_display();
+        display.spi.read_value = 0xFB;
+        assert_eq!(display.read_temperature().unwrap(), -5);
+        assert_eq!(display.spi.writes, [vec![CMD_TSC]]);
+
+        display.spi.read_value = 23;
+        assert_eq!(display.read_temperature().unwrap(), 23);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_error_defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+        assert_format::<Error<u8, u8>>();
+        assert_format::<Error<u8, u8, bool>>();
+    }
+
+    #[test]
+    fn test_distinct_busy_error_type() {
+        let mut display = Gdep073e01::new(
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            FaultyBusy,
+            MockDelay::default(),
+        );
+
+        let result: Result<bool, Error<MockError, MockError, BusyFault>> = display.busy_level();
+        assert!(matches!(result, Err(Error::Busy(BusyFault))));
+        assert!(matches!(display.init(), Err(Error::Busy(BusyFault))));
+    }
+
+    #[test]
+    fn test_portrait_transmits_physical_layout() {
+        use portrait::{PortraitGdep073e01, PORTRAIT_HEIGHT, PORTRAIT_WIDTH};
+
+        let mut display = PortraitGdep073e01::from_display(new_display());
+        assert_eq!(display.size(), Size::new(480, 800));
+        display.set_pixel(0, 0, Color::Red);
+        display.set_pixel(1, 0, Color::Blue);
+        display.set_pixel(0, 1, Color::Green);
+        display.set_pixel(PORTRAIT_WIDTH - 1, PORTRAIT_HEIGHT - 1, Color::Yellow);
+        display.set_pixel(PORTRAIT_WIDTH, 0, Color::Black);
+        Pixel(Point::new(100, 300), Color::Orange)
+            .draw(&mut display)
+            .unwrap();
+        display.flush().unwrap();
+
+        let frame = transmitted_frame(&display.into_inner().spi.writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        let nibble = |x: u32, y: u32| {
+            let byte = frame[(y * WIDTH + x) as usize / 2];
+            if x & 1 == 0 {
+                byte >> 4
+            } else {
+                byte & 0x0F
+            }
+        };
+        // Logical (x, y) lands at physical (y, HEIGHT - 1 - x)
+        assert_eq!(nibble(0, HEIGHT - 1), Color::Red as u8);
+        assert_eq!(nibble(0, HEIGHT - 2), Color::Blue as u8);
+        assert_eq!(nibble(1, HEIGHT - 1), Color::Green as u8);
+        assert_eq!(nibble(WIDTH - 1, 0), Color::Yellow as u8);
+        assert_eq!(nibble(300, HEIGHT - 101), Color::Orange as u8);
+        let colored = (0..WIDTH * HEIGHT)
+            .filter(|&i| nibble(i % WIDTH, i / WIDTH) != Color::White as u8)
+            .count();
+        assert_eq!(colored, 5);
+    }
+
+    #[test]
+    fn test_flush_from_iter() {
+        let palette = Color::ALL;
+        let pixel_count = (WIDTH * HEIGHT) as usize;
+        let pixels = (0..pixel_count).map(|i| palette[i % palette.len()]);
+
+        let mut reference = new_display();
+        for (i, color) in pixels.clone().enumerate() {
+            reference.set_pixel(i as u32 % WIDTH, i as u32 / WIDTH, color);
+        }
+
+        let mut display = new_display();
+        display.flush_from_iter(pixels, pixel_count).unwrap();
+
+        assert_eq!(
+            transmitted_frame(&display.spi.writes),
+            &reference.buffer[..]
+        );
+        assert_eq!(display.spi.writes.last().unwrap(), &[0x00]);
+        assert!(display.buffer.iter().all(|&byte| byte == 0x11));
+    }
+
+    #[test]
+    fn test_flush_from_iter_rejects_bad_count() {
+        let mut display = new_display();
+        let short = core::iter::repeat_n(Color::Red, 10);
+        assert!(matches!(
+            display.flush_from_iter(short.clone(), 10),
+            Err(Error::InvalidLength)
+        ));
+        assert!(matches!(
+            display.flush_from_iter(short, (WIDTH * HEIGHT) as usize),
+            Err(Error::InvalidLength)
+        ));
+        assert!(!display.spi.writes.contains(&vec![CMD_DISPLAY_REFRESH]));
+    }
+
+    #[test]
+    fn test_deghost() {
+        let mut display = new_display();
+        display.set_pixel(3, 4, Color::Green);
+        let before = display.buffer.to_vec();
+
+        display.deghost().unwrap();
+
+        let refreshes = display
+            .spi
+            .writes
+            .iter()
+            .filter(|w| w.as_slice() == [CMD_DISPLAY_REFRESH])
+            .count();
+        assert_eq!(refreshes, 8);
+        // The last color cycled is green, then the panel gets the buffer back
+        let writes = &display.spi.writes;
+        let last_frame = writes
+            .iter()
+            .rposition(|w| w.as_slice() == [CMD_DATA_START_TRANSMISSION])
+            .unwrap();
+        let last_color = transmitted_frame(&writes[..last_frame]);
+        assert!(last_color.iter().all(|&b| b == 0x66));
+        assert_eq!(transmitted_frame(writes), before);
+        assert_eq!(*display.buffer, *before);
+    }
+
+    #[test]
+    fn test_fill_solid_spans() {
+        let mut display = new_display();
+
+        // Byte-aligned span: whole bytes, neighbours untouched
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 0), Size::new(6, 1)),
+                Color::Red,
+            )
+            .unwrap();
+        assert_eq!(&display.buffer[1..6], &[0x11, 0x33, 0x33, 0x33, 0x11]);
+
+        // Ragged span: odd start and odd end keep the outer nibbles
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(1, 1), Size::new(4, 1)),
+                Color::Blue,
+            )
+            .unwrap();
+        let row = (WIDTH / 2) as usize;
+        assert_eq!(&display.buffer[row..row + 4], &[0x15, 0x55, 0x51, 0x11]);
+
+        // Single odd pixel
+        let mut reference = new_display();
+        reference.set_pixel(7, 2, Color::Green);
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(7, 2), Size::new(1, 1)),
+                Color::Green,
+            )
+            .unwrap();
+        assert_eq!(display.buffer[2 * row + 3], reference.buffer[2 * row + 3]);
+
+        // Aligned middle: one write per byte and no reads
+        let mut counting = Counting::new();
+        fill_span(&mut counting, 8, 408, 3, Color::Yellow);
+        assert_eq!(counting.writes, 200);
+        assert_eq!(counting.reads.get(), 0);
+        assert!(counting.bytes[3 * row + 4..3 * row + 204]
+            .iter()
+            .all(|&b| b == 0x22));
+
+        // Each ragged edge adds one read-modify-write
+        let mut counting = Counting::new();
+        fill_span(&mut counting, 7, 409, 3, Color::Yellow);
+        assert_eq!(counting.writes, 202);
+        assert_eq!(counting.reads.get(), 2);
+        assert_eq!(counting.bytes[3 * row + 3], 0x12);
+        assert_eq!(counting.bytes[3 * row + 204], 0x21);
+    }
+
+    #[test]
+    fn test_board_preset() {
+        let mut display = new_display();
+        assert!(display.busy_active_high);
+        assert_eq!(display.reset_delay_ms, 10);
+        assert_eq!(display.chunk_size, 4096);
+
+        display.set_chunk_size(64);
+        let display = display.with_defaults_for(BoardPreset::Generic);
+        assert!(display.busy_active_high);
+        assert_eq!(display.reset_delay_ms, 10);
+        assert_eq!(display.chunk_size, 4096);
+    }
+
+    #[test]
+    fn test_chunk_size() {
+        let mut display = new_display();
+        display.set_chunk_size(64_000);
+        display.flush().unwrap();
+        // Command, three chunks, refresh command and parameter
+        assert_eq!(display.spi.writes.len(), 1 + 3 + 2);
+        assert_eq!(display.spi.writes[1].len(), 64_000);
+
+        display.set_chunk_size(0);
+        assert_eq!(display.chunk_size, 1);
+        display.set_chunk_size(usize::MAX);
+        assert_eq!(display.chunk_size, BUFFER_SIZE);
+        display.spi.writes.clear();
+        display.flush().unwrap();
+        assert_eq!(display.spi.writes.len(), 1 + 1 + 2);
+    }
+
+    #[test]
+    fn test_flush_with_progress_estimate() {
+        let mut display = new_display();
+        display.set_spi_clock_hint(8_000_000);
+
+        let mut updates = Vec::new();
+        display
+            .flush_with_progress(|sent, total, est| updates.push((sent, total, est)))
+            .unwrap();
+
+        assert_eq!(updates.len(), BUFFER_SIZE.div_ceil(display.chunk_size));
+        assert!(updates.windows(2).all(|w| w[1].2 < w[0].2));
+        // The final 3584-byte chunk takes ~3.6 ms at 8 MHz
+        assert_eq!(updates[updates.len() - 2].2, 4);
+        assert_eq!(updates.last(), Some(&(BUFFER_SIZE, BUFFER_SIZE, 0)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_color_serde_round_trip() {
+        for color in Color::ALL {
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+        }
+        assert_eq!(serde_json::to_string(&Color::Orange).unwrap(), "\"orange\"");
+    }
+
+    /// Decodes the nibble at `(x, y)` straight from the buffer.
+    fn nibble<SPI, CS, DC, RST, BUSY, DELAY>(
+        display: &Gdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY>,
+        x: u32,
+        y: u32,
+    ) -> u8 {
+        let byte = display.buffer[((y * WIDTH + x) / 2) as usize];
+        if x & 1 == 0 {
+            byte >> 4
+        } else {
+            byte & 0x0F
+        }
+    }
+
+    #[test]
+    fn test_fill_circle_spans() {
+        let mut display = new_display();
+        display.fill_circle(Point::new(10, 10), 2, Color::Red);
+
+        // Expected midpoint rasterization for r = 2
+        let spans = [
+            (8, 9, 11),
+            (9, 8, 12),
+            (10, 8, 12),
+            (11, 8, 12),
+            (12, 9, 11),
+        ];
+        for y in 6..15 {
+            let span = spans.iter().find(|s| s.0 == y);
+            for x in 6..15 {
+                let inside = span.is_some_and(|&(_, x0, x1)| (x0..=x1).contains(&x));
+                let expected = if inside { Color::Red } else { Color::White };
+                assert_eq!(nibble(&display, x, y), expected as u8, "pixel ({x}, {y})");
+            }
+        }
+
+        // Clipped at the corner without panicking
+        display.fill_circle(Point::new(0, 0), 3, Color::Blue);
+        assert_eq!(nibble(&display, 0, 0), Color::Blue as u8);
+        assert_eq!(nibble(&display, 3, 0), Color::Blue as u8);
+        assert_eq!(nibble(&display, 4, 0), Color::White as u8);
+
+        // Rotated: the center is logical, like set_pixel
+        let mut rotated = new_display();
+        rotated.set_rotation(Rotation::Deg270);
+        rotated.fill_circle(Point::new(10, 700), 2, Color::Red);
+        let mut expected = new_display();
+        expected.set_rotation(Rotation::Deg270);
+        for &(y, x0, x1) in &spans {
+            for x in x0..=x1 {
+                expected.set_pixel(x, y + 690, Color::Red);
+            }
+        }
+        assert_eq!(*rotated.buffer, *expected.buffer);
+
+        // Row widths match a midpoint walk for every radius
+        for r in 0..300u32 {
+            let mut widths = vec![0; r as usize + 1];
+            let (mut x, mut y, mut err) = (r as i64, 0i64, 1 - r as i64);
+            while x >= y {
+                widths[y as usize] = widths[y as usize].max(x as u64);
+                widths[x as usize] = widths[x as usize].max(y as u64);
+                y += 1;
+                if err < 0 {
+                    err += 2 * y + 1;
+                } else {
+                    x -= 1;
+                    err += 2 * (y - x) + 1;
+                }
+            }
+            for (d, &width) in widths.iter().enumerate() {
+                assert_eq!(
+                    circle_half_width(r.into(), d as u64),
+                    width,
+                    "r {r}, row {d}"
+                );
+            }
+        }
+
+        // Extreme centers and radii neither overflow nor walk off-screen rows
+        let mut display = new_display();
+        display.fill_circle(Point::new(i32::MAX, 0), 1, Color::Red);
+        display.fill_circle(Point::new(i32::MIN, 240), 1 << 30, Color::Red);
+        assert!(display.buffer.iter().all(|&b| b == 0x11));
+        display.fill_circle(Point::new(400, 240), u32::MAX, Color::Green);
+        assert!(display.buffer.iter().all(|&b| b == 0x66));
+    }
+
+    #[test]
+    fn test_init_soft_skips_rst() {
+        let mut display = new_display();
+        display.init_soft().unwrap();
+
+        assert!(display.rst.states.is_empty());
+        assert_eq!(display.spi.writes[0], [CMD_POWER_OFF]);
+        assert_eq!(display.spi.writes[2], [CMD_CMDH]);
+        assert_eq!(display.spi.writes.last().unwrap(), &[CMD_POWER_ON]);
+    }
+
+    #[test]
+    fn test_standby_resume() {
+        let mut display = new_display();
+        display.init().unwrap();
+        display.spi.writes.clear();
+
+        display.standby().unwrap();
+        assert_eq!(display.spi.writes, [vec![CMD_POWER_OFF], vec![0x00]]);
+        assert!(!display.spi.writes.contains(&vec![CMD_DEEP_SLEEP]));
+
+        display.spi.writes.clear();
+        display.resume().unwrap();
+        assert_eq!(display.spi.writes, [vec![CMD_POWER_ON]]);
+        // Only the reset from init
+        assert_eq!(display.rst.states, [PinState::Low, PinState::High]);
+
+        // Deep sleep adds the sleep command after powering off
+        display.spi.writes.clear();
+        display.sleep().unwrap();
+        assert_eq!(
+            display.spi.writes,
+            [
+                vec![CMD_POWER_OFF],
+                vec![0x00],
+                vec![CMD_DEEP_SLEEP],
+                vec![0xA5]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_upload_then_refresh() {
+        let mut display = new_display();
+        display.set_pixel(0, 0, Color::Red);
+        display.upload().unwrap();
+        assert_eq!(display.spi.writes[0], [CMD_DATA_START_TRANSMISSION]);
+        assert_eq!(display.spi.writes.concat().len(), 1 + BUFFER_SIZE);
+        assert!(!display.spi.writes.contains(&vec![CMD_DISPLAY_REFRESH]));
+
+        let mut uploaded = core::mem::take(&mut display.spi.writes);
+        display.refresh().unwrap();
+        assert_eq!(
+            display.spi.writes,
+            [vec![CMD_DISPLAY_REFRESH], vec![REFRESH_FULL]]
+        );
+
+        // flush() sends the same as both together
+        uploaded.append(&mut display.spi.writes);
+        display.flush().unwrap();
+        assert_eq!(display.spi.writes, uploaded);
+    }
+
+    #[cfg(feature = "overlay")]
+    #[test]
+    fn test_overlay() {
+        let mut display = new_display();
+        display.fill_span(0, 8, 0, Color::Blue);
+        display.overlay_set_pixel(1, 0, Color::Red);
+        display.overlay_set_pixel(2, 0, Color::Black);
+        display.overlay_set_pixel(2, 1, Color::Yellow);
+        display.flush().unwrap();
+
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        assert_eq!(frame[..4], [0x53, 0x05, 0x55, 0x55]);
+        assert_eq!(frame[WIDTH as usize / 2 + 1], 0x21);
+        // The buffer keeps the base image
+        assert_eq!(display.buffer[..2], [0x55, 0x55]);
+        let wire: Vec<Vec<u8>> = display.flush_bytes().into_iter().map(|(_, b)| b).collect();
+        assert_eq!(transmitted_frame(&wire), frame);
+
+        // Cleared overlay pixels are transparent again
+        display.overlay_clear();
+        display.flush().unwrap();
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame[..2], [0x55, 0x55]);
+        assert_eq!(frame[WIDTH as usize / 2 + 1], 0x11);
+    }
+
+    #[test]
+    fn test_cmdh() {
+        let mut display = new_display();
+        display.init().unwrap();
+        assert_eq!(display.spi.writes[0], [CMD_CMDH]);
+        assert_eq!(display.spi.writes[1], CMDH_DATA);
+
+        let magic = [0x49, 0x55, 0x20, 0x08, 0x09, 0x20];
+        display.set_cmdh(magic);
+        display.spi.writes.clear();
+        display.init().unwrap();
+        assert_eq!(display.spi.writes[0], [CMD_CMDH]);
+        assert_eq!(display.spi.writes[1], magic);
+    }
+
+    #[test]
+    fn test_fill_contiguous_matches_draw_iter() {
+        let areas = [
+            Rectangle::new(Point::new(3, 2), Size::new(7, 5)),
+            Rectangle::new(Point::new(-3, -2), Size::new(8, 4)),
+            Rectangle::new(Point::new(WIDTH as i32 - 5, 10), Size::new(9, 3)),
+        ];
+        for area in areas {
+            let colors = || {
+                (0..area.size.width * area.size.height)
+                    .map(|i| Color::ALL[i as usize % Color::ALL.len()])
+            };
+
+            let mut expected = new_display();
+            let pixels = area.points().zip(colors()).map(|(p, c)| Pixel(p, c));
+            expected.draw_iter(pixels).unwrap();
+
+            let mut display = new_display();
+            display.fill_contiguous(&area, colors()).unwrap();
+
+            assert_eq!(*display.buffer, *expected.buffer, "area {area:?}");
+        }
+    }
+
+    #[test]
+    fn test_busy_waiter_replaces_polling() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        let mut display = new_display();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        display.set_busy_waiter(move |max_ms| {
+            assert_eq!(
+                max_ms,
+                BUSY_TIMEOUT_MS - counter.load(Ordering::Relaxed) as u32
+            );
+            counter.fetch_add(1, Ordering::Relaxed);
+            Ok(1)
+        });
+
+        display.busy.high_reads = 3;
+        display.flush().unwrap();
+
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert!(display.delay.delays_ns.is_empty());
+
+        // Time is charged as the waiter reports it: a 1 ms wake-up does not
+        // exhaust the timeout early, one long block exhausts it at once
+        display.set_busy_timeout_ms(50);
+        display.set_busy_waiter(|_| Ok(1));
+        display.busy.high_reads = 49;
+        display.flush().unwrap();
+        display.set_busy_waiter(Ok);
+        display.busy.high_reads = usize::MAX;
+        assert!(matches!(display.flush(), Err(Error::Timeout)));
+
+        display.set_busy_waiter(|_| Err(()));
+        display.busy.high_reads = 1;
+        assert!(matches!(display.flush(), Err(Error::WaiterFailed)));
+    }
+
+    #[test]
+    fn test_flush_with_poll() {
+        let mut display = new_display();
+        display.busy.high_reads = 4;
+        let mut polls = 0;
+        display.flush_with_poll(|| polls += 1).unwrap();
+        assert_eq!(polls, 4);
+        assert_eq!(display.delay.delays_ns.len(), 4);
+        assert_eq!(transmitted_frame(&display.spi.writes).len(), BUFFER_SIZE);
+
+        // Idle at once: no polls
+        let mut polls = 0;
+        display.flush_with_poll(|| polls += 1).unwrap();
+        assert_eq!(polls, 0);
+    }
+
+    #[test]
+    fn test_set_pixel_unchecked_matches_set_pixel() {
+        let mut checked = new_display();
+        let mut unchecked = new_display();
+        let mut color = Color::Black;
+        for (x, y) in [
+            (0, 0),
+            (1, 0),
+            (2, 7),
+            (WIDTH - 1, 0),
+            (0, HEIGHT - 1),
+            (WIDTH - 1, HEIGHT - 1),
+        ] {
+            for _ in 0..3 {
+                checked.set_pixel(x, y, color);
+                // SAFETY: all coordinates above are in range
+                unsafe { unchecked.set_pixel_unchecked(x, y, color) };
+                color = color.next();
+            }
+        }
+        assert_eq!(*checked.buffer, *unchecked.buffer);
+    }
+
+    #[test]
+    fn test_fill_dithered() {
+        let area = Rectangle::new(Point::new(3, 2), Size::new(8, 6));
+        let fill = |ratio| {
+            let mut display = new_display();
+            display.fill_dithered(&area, Color::Blue, Color::Yellow, ratio);
+            assert_eq!(
+                display.nibble_at(Point::new(2, 2)),
+                Some(Color::White as u8)
+            );
+            assert_eq!(
+                display.nibble_at(Point::new(3, 8)),
+                Some(Color::White as u8)
+            );
+            area.points()
+                .map(|p| (p, display.nibble_at(p).unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(fill(0).iter().all(|&(_, c)| c == Color::Blue as u8));
+        assert!(fill(255).iter().all(|&(_, c)| c == Color::Yellow as u8));
+
+        let half = fill(128);
+        for &(p, c) in &half {
+            let expected = if (p.x + p.y) & 1 == 0 {
+                Color::Yellow
+            } else {
+                Color::Blue
+            };
+            assert_eq!(c, expected as u8, "{p:?}");
+        }
+
+        // Clipped at the display edge
+        let mut display = new_display();
+        let edge = Rectangle::new(Point::new(WIDTH as i32 - 2, -2), Size::new(8, 4));
+        display.fill_dithered(&edge, Color::Red, Color::Red, 0);
+        assert_eq!(display.buffer[WIDTH as usize / 2 - 1], 0x33);
+        assert_eq!(display.buffer[WIDTH as usize - 1], 0x33);
+        assert_eq!(display.buffer[WIDTH as usize], 0x11);
+
+        // Rotated: clipped against the logical size, pattern in logical coordinates
+        let mut display = new_display();
+        display.set_rotation(Rotation::Deg90);
+        let tall = Rectangle::new(Point::new(3, HEIGHT as i32 + 100), Size::new(8, 6));
+        display.fill_dithered(&tall, Color::Blue, Color::Yellow, 128);
+        for p in tall.points() {
+            let expected = if (p.x + p.y) & 1 == 0 {
+                Color::Yellow
+            } else {
+                Color::Blue
+            };
+            assert_eq!(
+                display.get_pixel(p.x as u32, p.y as u32),
+                Some(expected),
+                "{p:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_busy_timeout_ms() {
+        let mut display = new_display();
+        display.set_busy_timeout_ms(25);
+        display.busy.high_reads = usize::MAX;
+        assert!(matches!(display.flush(), Err(Error::Timeout)));
+        assert_eq!(display.delay.delays_ns, [10_000_000, 10_000_000, 5_000_000]);
+
+        // 0 waits as long as BUSY stays asserted
+        display.set_busy_timeout_ms(0);
+        display.busy.high_reads = 5000;
+        display.delay.delays_ns.clear();
+        display.flush().unwrap();
+        assert_eq!(display.delay.delays_ns.len(), 5000);
+    }
+
+    #[test]
+    fn test_busy_ack_timeout() {
+        let mut display = new_display();
+        display.set_busy_ack_timeout(Some(5));
+
+        // BUSY stays low: the refresh was never acknowledged
+        assert!(matches!(display.flush(), Err(Error::BusyNeverAsserted)));
+        assert_eq!(display.delay.delays_ns, [1_000_000; 5]);
+        let writes = &display.spi.writes;
+        assert_eq!(
+            writes[writes.len() - 2..],
+            [vec![CMD_DISPLAY_REFRESH], vec![0x00]]
+        );
+
+        // BUSY asserts, then the usual idle wait follows
+        display.spi.writes.clear();
+        display.delay.delays_ns.clear();
+        display.busy.high_reads = 2;
+        display.flush().unwrap();
+        assert_eq!(display.delay.delays_ns, [BUSY_WAIT_DELAY_MS * 1_000_000]);
+
+        display.set_busy_ack_timeout(None);
+        display.flush().unwrap();
+    }
+
+    #[test]
+    fn test_refresh_param() {
+        let mut display = new_display();
+        display.flush().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_DISPLAY_REFRESH),
+            [REFRESH_FULL]
+        );
+
+        display.set_refresh_param(0x01);
+        display.spi.writes.clear();
+        display.flush().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_DISPLAY_REFRESH),
+            [0x01]
+        );
+
+        display.spi.writes.clear();
+        display.clear_and_flush(Color::White).unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_DISPLAY_REFRESH),
+            [0x01]
+        );
+    }
+
+    #[test]
+    fn test_clear_and_flush_sequence() {
+        let mut display = new_display();
+        display.clear_and_flush(Color::Yellow).unwrap();
+
+        let writes = &display.spi.writes;
+        assert_eq!(writes[0], [CMD_DATA_START_TRANSMISSION]);
+        assert_eq!(
+            &writes[writes.len() - 2..],
+            &[vec![CMD_DISPLAY_REFRESH], vec![0x00]]
+        );
+        let frame = transmitted_frame(writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        assert!(frame.iter().all(|&byte| byte == 0x22));
+        assert!(display.buffer.iter().all(|&byte| byte == 0x22));
+
+        // Same bytes as `clear_buffer` + `flush`, color map included
+        let mut map = Color::ALL;
+        map[Color::Yellow as usize] = Color::Red;
+        let mut display = new_display();
+        display.set_color_map(map);
+        display.clear_and_flush(Color::Yellow).unwrap();
+        let mut reference = new_display();
+        reference.set_color_map(map);
+        reference.clear_buffer(Color::Yellow);
+        reference.flush().unwrap();
+        assert_eq!(display.spi.writes, reference.spi.writes);
+        assert!(transmitted_frame(&display.spi.writes)
+            .iter()
+            .all(|&byte| byte == 0x33));
+    }
+
+    #[test]
+    fn test_from_embedded_graphics_colors() {
+        assert_eq!(Color::from(BinaryColor::On), Color::Black);
+        assert_eq!(Color::from(BinaryColor::Off), Color::White);
+        assert_eq!(
+            Color::from_binary(BinaryColor::On, Color::Blue, Color::Yellow),
+            Color::Blue
+        );
+
+        let ramp: Vec<Color> = (0..4).map(|l| Color::from(Gray2::new(l))).collect();
+        assert_eq!(
+            ramp,
+            [Color::Black, Color::Red, Color::Orange, Color::White]
+        );
+    }
+
+    #[test]
+    fn test_flush_step_resumes() {
+        use core::cell::Cell;
+
+        let mut display = new_display();
+        display.set_pixel(WIDTH - 1, HEIGHT - 1, Color::Blue);
+        display.busy.high_reads = 2;
+
+        let mut progress = Vec::new();
+        loop {
+            // Allow ten chunks per call
+            let chunks = Cell::new(0);
+            let budget = || {
+                chunks.set(chunks.get() + 1);
+                chunks.get() < 10
+            };
+            let step = display.flush_step(budget).unwrap();
+            progress.push(step);
+            if step == FlushProgress::Done {
+                break;
+            }
+        }
+
+        // 47 chunks at ten per call, then two busy polls
+        use FlushProgress::*;
+        assert_eq!(
+            progress,
+            [
+                InProgress, InProgress, InProgress, InProgress, Refreshing, Refreshing, Refreshing,
+                Done
+            ]
+        );
+        assert_eq!(transmitted_frame(&display.spi.writes), &display.buffer[..]);
+        assert_eq!(display.flush_state, FlushState::Idle);
+    }
+
+    #[test]
+    fn test_draw_rect_outline() {
+        let mut display = new_display();
+        display.draw_rect_outline(
+            &Rectangle::new(Point::new(5, 5), Size::new(10, 10)),
+            Color::Black,
+            1,
+        );
+
+        for y in 0..20 {
+            for x in 0..20 {
+                let inside = (5..15).contains(&x) && (5..15).contains(&y);
+                let border = inside && (x == 5 || x == 14 || y == 5 || y == 14);
+                let expected = if border { Color::Black } else { Color::White };
+                assert_eq!(nibble(&display, x, y), expected as u8, "pixel ({x}, {y})");
+            }
+        }
+
+        // Clipped and thick outlines don't panic
+        display.draw_rect_outline(
+            &Rectangle::new(Point::new(-4, -4), Size::new(8, 8)),
+            Color::Red,
+            3,
+        );
+        // Visible part spans 0..=3 with the inner edge at 0..=0
+        assert_eq!(nibble(&display, 0, 0), Color::White as u8);
+        assert_eq!(nibble(&display, 1, 0), Color::Red as u8);
+        assert_eq!(nibble(&display, 0, 1), Color::Red as u8);
+        assert_eq!(nibble(&display, 4, 0), Color::White as u8);
+
+        // Huge rectangles only visit the rows on the display
+        let mut display = new_display();
+        let huge = Rectangle::new(Point::new(-3, -5), Size::new(10, u32::MAX));
+        display.draw_rect_outline(&huge, Color::Red, 2);
+        assert_eq!(nibble(&display, 5, 0), Color::Red as u8);
+        assert_eq!(nibble(&display, 6, HEIGHT - 1), Color::Red as u8);
+        assert_eq!(nibble(&display, 4, HEIGHT - 1), Color::White as u8);
+        assert_eq!(nibble(&display, 0, HEIGHT - 1), Color::White as u8);
+
+        // Rotated: the rectangle is logical, like set_pixel
+        let mut rotated = new_display();
+        rotated.set_rotation(Rotation::Deg90);
+        let area = Rectangle::new(Point::new(5, 600), Size::new(10, 10));
+        rotated.draw_rect_outline(&area, Color::Black, 1);
+        for p in area.points() {
+            let border = p.x == 5 || p.x == 14 || p.y == 600 || p.y == 609;
+            let expected = if border { Color::Black } else { Color::White };
+            assert_eq!(
+                rotated.get_pixel(p.x as u32, p.y as u32),
+                Some(expected),
+                "{p:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_busy_level_is_raw() {
+        let mut display = new_display();
+        display.busy_active_high = false;
+        display.busy.high_reads = 1;
+        assert!(display.busy_level().unwrap());
+        assert!(!display.busy_level().unwrap());
+    }
+
+    #[test]
+    fn test_event_sink_sequence() {
+        extern crate std;
+        use alloc::sync::Arc;
+        use std::sync::Mutex;
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let mut display = new_display();
+        display.set_event_sink(move |e| sink.lock().unwrap().push(e));
+
+        display.busy.high_reads = 3;
+        display.flush().unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                FlushEvent::TransmitStart { bytes: BUFFER_SIZE },
+                FlushEvent::TransmitEnd { bytes: BUFFER_SIZE },
+                FlushEvent::RefreshStart,
+                FlushEvent::RefreshEnd { busy_ms: 30 },
+            ]
+        );
+
+        events.lock().unwrap().clear();
+        display.set_busy_timeout_ms(20);
+        display.set_busy_waiter(Ok);
+        display.busy.high_reads = usize::MAX;
+        assert!(display.flush().is_err());
+        assert_eq!(
+            events.lock().unwrap().last(),
+            Some(&FlushEvent::Timeout { waited_ms: 20 })
+        );
+    }
+
+    #[test]
+    fn test_dissolve_toward() {
+        let target = vec![0x33; BUFFER_SIZE];
+        let mut display = new_display();
+
+        display.dissolve_toward(&target, 0).unwrap();
+        assert!(display.buffer.iter().all(|&byte| byte == 0x11));
+
+        display.dissolve_toward(&target, 128).unwrap();
+        let red = (0..16)
+            .filter(|&i| nibble(&display, i % 4, i / 4) == Color::Red as u8)
+            .count();
+        assert_eq!(red, 8);
+
+        display.dissolve_toward(&target, 255).unwrap();
+        assert_eq!(&display.buffer[..], &target[..]);
+
+        assert!(matches!(
+            display.dissolve_toward(&target[1..], 255),
+            Err(Error::InvalidLength)
+        ));
+    }
+
+    /// Returns the data bytes sent after the first occurrence of `command`.
+    fn command_data(writes: &[Vec<u8>], command: u8) -> Vec<u8> {
+        let index = writes
+            .iter()
+            .position(|w| w.as_slice() == [command])
+            .expect("command not sent");
+        writes[index + 1].clone()
+    }
+
+    #[test]
+    fn test_booster_profile() {
+        let mut display = new_display();
+        display.set_booster_profile(BoosterProfile::Quality);
+        display.init().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_BOOSTER_SOFT_START1),
+            [0x40, 0x1F, 0x1F, 0x2C]
+        );
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_BOOSTER_SOFT_START2),
+            [0x6F, 0x1F, 0x17, 0x49]
+        );
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_BOOSTER_SOFT_START3),
+            [0x6F, 0x1F, 0x1F, 0x22]
+        );
+    }
+
+    #[test]
+    fn test_draw_line_bresenham() {
+        let cases = [
+            (
+                Point::new(1, 1),
+                Point::new(7, 4),
+                &[(1, 1), (2, 1), (3, 2), (4, 2), (5, 3), (6, 3), (7, 4)][..],
+            ),
+            (
+                Point::new(2, 1),
+                Point::new(4, 8),
+                &[
+                    (2, 1),
+                    (2, 2),
+                    (3, 3),
+                    (3, 4),
+                    (3, 5),
+                    (3, 6),
+                    (4, 7),
+                    (4, 8),
+                ],
+            ),
+        ];
+
+        for (start, end, expected) in cases {
+            for (a, b) in [(start, end), (end, start)] {
+                let mut display = new_display();
+                display.draw_line(a, b, Color::Black);
+                for y in 0..10 {
+                    for x in 0..10 {
+                        let on = expected.contains(&(x, y));
+                        let color = if on { Color::Black } else { Color::White };
+                        assert_eq!(nibble(&display, x, y), color as u8, "pixel ({x}, {y})");
+                    }
+                }
+            }
+        }
+
+        // Partially off-screen lines are clipped
+        let mut display = new_display();
+        display.draw_line(Point::new(-5, 0), Point::new(5, 0), Color::Red);
+        assert_eq!(nibble(&display, 0, 0), Color::Red as u8);
+        assert_eq!(nibble(&display, 5, 0), Color::Red as u8);
+        assert_eq!(nibble(&display, 6, 0), Color::White as u8);
+
+        // Clipping keeps the pixels of the full walk
+        let walk = |start: Point, end: Point| {
+            let mut display = new_display();
+            let (dx, dy) = (end.x - start.x, (end.y - start.y).abs());
+            let mut err = dx / 2;
+            let mut y = start.y;
+            for x in start.x..=end.x {
+                if x >= 0 && y >= 0 {
+                    display.set_pixel(x as u32, y as u32, Color::Blue);
+                }
+                err -= dy;
+                if err < 0 {
+                    y += (end.y - start.y).signum();
+                    err += dx;
+                }
+            }
+            display
+        };
+        for (start, end) in [
+            (Point::new(-300, -40), Point::new(900, 500)),
+            (Point::new(-7, 30), Point::new(1000, 2)),
+        ] {
+            let mut display = new_display();
+            display.draw_line(start, end, Color::Blue);
+            assert_eq!(*display.buffer, *walk(start, end).buffer, "{start:?}");
+        }
+
+        // Far-apart endpoints neither overflow nor walk off-screen points
+        let mut display = new_display();
+        display.draw_line(
+            Point::new(i32::MIN, 3),
+            Point::new(i32::MAX, 3),
+            Color::Green,
+        );
+        let row = WIDTH as usize / 2;
+        assert!(display.buffer[3 * row..4 * row].iter().all(|&b| b == 0x66));
+        display.draw_line(
+            Point::new(i32::MIN, i32::MIN),
+            Point::new(i32::MAX, i32::MAX),
+            Color::Red,
+        );
+        assert_eq!(display.get_pixel(0, 0), Some(Color::Red));
+        assert_eq!(display.get_pixel(HEIGHT - 1, HEIGHT - 1), Some(Color::Red));
+        display.draw_line(
+            Point::new(0, i32::MIN),
+            Point::new(1, i32::MAX),
+            Color::Black,
+        );
+    }
+
+    /// 8x4 color gradient for `present_golden`.
+    #[cfg(feature = "pal-spectra6")]
+    fn golden_gradient() -> Vec<[u8; 3]> {
+        (0..32u32)
+            .map(|i| {
+                let (x, y) = (i % 8, i / 8);
+                [
+                    (96 + x * 20) as u8,
+                    (90 + y * 24) as u8,
+                    (160 - x * 12) as u8,
+                ]
+            })
+            .collect()
+    }
+
+    /// Top-left 8x4 pixels of the buffer after presenting the 8-pixel-wide `src`.
+    #[cfg(feature = "pal-spectra6")]
+    fn present_golden<S: dither::DitherStrategy>(src: &[[u8; 3]], strat: &mut S) -> [[u8; 4]; 4] {
+        let mut display = new_display();
+        display.present_rgb(src, 8, strat).unwrap();
+        let mut rows = [[0u8; 4]; 4];
+        for (y, row) in rows.iter_mut().enumerate() {
+            let start = y * (WIDTH as usize / 2);
+            row.copy_from_slice(&display.buffer[start..start + 4]);
+        }
+        rows
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_bake_matches_present_rgb() {
+        let (width, height) = (13u32, 6u32);
+        let src: Vec<[u8; 3]> = (0..width * height)
+            .map(|i| {
+                let (x, y) = (i % width, i / width);
+                [(x * 19) as u8, (200 - y * 30) as u8, (x * y * 3) as u8]
+            })
+            .collect();
+
+        let mut expected = new_display();
+        expected
+            .present_rgb(&src, width, &mut dither::Nearest)
+            .unwrap();
+
+        let mut display = new_display();
+        let frame = display
+            .bake(&src, width, height, &mut dither::Nearest)
+            .unwrap();
+        assert_eq!((frame.width(), frame.height()), (width, height));
+        assert!(display.spi.writes.is_empty());
+        display.present_baked(&frame).unwrap();
+        assert_eq!(*display.buffer, *expected.buffer);
+        assert_eq!(display.spi.writes, expected.spi.writes);
+
+        #[cfg(feature = "dither-fs")]
+        {
+            let mut fs = dither::FloydSteinberg::new(width);
+            let mut expected = new_display();
+            expected.present_rgb(&src, width, &mut fs).unwrap();
+            let frame = display.bake(&src, width, height, &mut fs).unwrap();
+            display.present_baked(&frame).unwrap();
+            assert_eq!(*display.buffer, *expected.buffer);
+        }
+
+        assert!(matches!(
+            display.bake(&src, width, height + 1, &mut dither::Nearest),
+            Err(Error::InvalidLength)
+        ));
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_dither_target_calibration() {
+        type Target = adapter::DitherDrawTarget<
+            Gdep073e01<'static, MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay>,
+            dither::Nearest,
+        >;
+        fn draw(target: &mut Target, x: i32, rgb: Rgb888) -> u8 {
+            Pixel(Point::new(x, 0), rgb).draw(target).unwrap();
+            target.display_mut().nibble_at(Point::new(x, 0)).unwrap()
+        }
+
+        let mut target = new_display().into_dither_target(dither::Nearest);
+        let warm = Rgb888::new(250, 130, 5);
+        let default = draw(&mut target, 0, warm);
+        assert_ne!(default, Color::Orange as u8);
+
+        target.set_calibration(&[
+            (Rgb888::new(255, 128, 0), Color::Orange),
+            (Rgb888::new(40, 40, 40), Color::Blue),
+        ]);
+        assert_eq!(draw(&mut target, 1, warm), Color::Orange as u8);
+        assert_eq!(
+            draw(&mut target, 2, Rgb888::new(30, 45, 40)),
+            Color::Blue as u8
+        );
+        // Far from every entry: the strategy decides
+        assert_eq!(
+            draw(&mut target, 3, Rgb888::new(0, 200, 0)),
+            Color::Green as u8
+        );
+
+        target.set_calibration(&[]);
+        assert_eq!(draw(&mut target, 4, warm), default);
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_dither_target_clips_to_display() {
+        /// Records the largest coordinates it is asked to map.
+        #[derive(Default)]
+        struct Extent(u32, u32);
+        impl dither::DitherStrategy for Extent {
+            fn map(&mut self, x: u32, y: u32, rgb: [u8; 3]) -> palette::Spectra6 {
+                self.0 = self.0.max(x);
+                self.1 = self.1.max(y);
+                palette::map_rgb_to_spectra6_nearest(rgb)
+            }
+        }
+
+        let mut target = new_display().into_dither_target(Extent::default());
+        let wide = Rectangle::new(Point::new(-5, HEIGHT as i32 - 2), Size::new(WIDTH * 4, 8));
+        target.fill_solid(&wide, Rgb888::BLACK).unwrap();
+        Pixel(Point::new(WIDTH as i32 + 3, 0), Rgb888::BLACK)
+            .draw(&mut target)
+            .unwrap();
+
+        let strat = target.strategy_mut();
+        assert_eq!((strat.0, strat.1), (WIDTH - 1, HEIGHT - 1));
+        let display = target.into_inner();
+        let (rest, bottom) = display.buffer.split_at(BUFFER_SIZE - WIDTH as usize);
+        assert!(bottom.iter().all(|&b| b == 0x00));
+        assert!(rest.iter().all(|&b| b == 0x11));
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_show_image() {
+        // 3x2: red, green, blue / white, black, yellow
+        const DATA: [u8; 18] = [
+            255, 0, 0, 0, 255, 0, 0, 0, 255, //
+            255, 255, 255, 0, 0, 0, 255, 255, 0,
+        ];
+        let img = ImageRaw::<Rgb888>::new(&DATA, 3);
+
+        let mut display = new_display();
+        display
+            .show_image(&img, Point::new(1, 1), &mut dither::Nearest)
+            .unwrap();
+
+        let row = WIDTH as usize / 2;
+        assert_eq!(display.buffer[..2], [0x11, 0x11]);
+        assert_eq!(display.buffer[row..row + 2], [0x13, 0x65]);
+        assert_eq!(display.buffer[2 * row..2 * row + 2], [0x11, 0x02]);
+        assert!(display.spi.writes.contains(&vec![CMD_DISPLAY_REFRESH]));
+
+        // Partially off-screen images are clipped
+        let mut display = new_display();
+        display
+            .show_image(
+                &img,
+                Point::new(-2, HEIGHT as i32 - 1),
+                &mut dither::Nearest,
+            )
+            .unwrap();
+        let last = (HEIGHT as usize - 1) * row;
+        assert_eq!(display.buffer[last], 0x51);
+        assert!(display.buffer[..last].iter().all(|&b| b == 0x11));
+
+        // Rotated: logical rows past the physical height are drawn
+        let colors = [
+            Color::Red,
+            Color::Green,
+            Color::Blue,
+            Color::White,
+            Color::Black,
+            Color::Yellow,
+        ];
+        for rotation in [Rotation::Deg90, Rotation::Deg270] {
+            let mut display = new_display();
+            display.set_rotation(rotation);
+            let top_left = Point::new(1, HEIGHT as i32 + 100);
+            display
+                .show_image(&img, top_left, &mut dither::Nearest)
+                .unwrap();
+            let mut expected = new_display();
+            expected.set_rotation(rotation);
+            for (i, &color) in colors.iter().enumerate() {
+                expected.set_pixel(1 + i as u32 % 3, HEIGHT + 100 + i as u32 / 3, color);
+            }
+            assert_eq!(*display.buffer, *expected.buffer, "{rotation:?}");
+        }
+    }
+
+    #[cfg(feature = "jpeg")]
+    #[test]
+    fn test_draw_jpeg() {
+        use jpeg_encoder::{ColorType, Encoder, SamplingFactor};
+
+        // 16x16, red left half and blue right half, yellow bottom rows
+        let (w, h) = (16u16, 16u16);
+        let mut rgb = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                rgb.extend_from_slice(match (x < 8, y < 12) {
+                    (_, false) => &[250, 230, 0],
+                    (true, true) => &[220, 10, 10],
+                    (false, true) => &[10, 20, 220],
+                });
+            }
+        }
+        let encode = |progressive| {
+            let mut jpeg = Vec::new();
+            let mut encoder = Encoder::new(&mut jpeg, 90);
+            encoder.set_sampling_factor(SamplingFactor::R_4_2_0);
+            encoder.set_progressive(progressive);
+            encoder.encode(&rgb, w, h, ColorType::Rgb).unwrap();
+            jpeg
+        };
+
+        let mut display = new_display();
+        let origin = Point::new(-4, 2);
+        display
+            .draw_jpeg(&encode(false), origin, &mut dither::Nearest)
+            .unwrap();
+        let at = |x, y| display.nibble_at(origin + Point::new(x, y));
+        assert_eq!(at(6, 4), Some(Color::Red as u8));
+        assert_eq!(at(13, 4), Some(Color::Blue as u8));
+        assert_eq!(at(6, 14), Some(Color::Yellow as u8));
+        assert_eq!(
+            display.nibble_at(Point::new(12, 8)),
+            Some(Color::White as u8)
+        );
+        assert_eq!(
+            display.nibble_at(Point::new(0, 1)),
+            Some(Color::White as u8)
+        );
+        assert!(display.spi.writes.is_empty());
+
+        let mut display = new_display();
+        for data in [encode(true), b"not a jpeg".to_vec()] {
+            assert!(matches!(
+                display.draw_jpeg(&data, Point::zero(), &mut dither::Nearest),
+                Err(Error::UnsupportedFormat)
+            ));
+        }
+        assert!(display.buffer.iter().all(|&b| b == 0x11));
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_present_rgb_golden() {
+        const NEAREST: [[u8; 4]; 4] = [
+            [0x55, 0x33, 0x33, 0x33],
+            [0x55, 0x33, 0x33, 0x33],
+            [0x66, 0x12, 0x22, 0x22],
+            [0x66, 0x12, 0x22, 0x22],
+        ];
+        let gradient = golden_gradient();
+        assert_eq!(present_golden(&gradient, &mut dither::Nearest), NEAREST);
+
+        #[cfg(feature = "dither-bayer")]
+        {
+            // A gray ramp across the black/white threshold, where the bias shows
+            let ramp: Vec<[u8; 3]> = (0..32u8).map(|i| [124 + i % 8; 3]).collect();
+            const RAMP_NEAREST: [[u8; 4]; 4] = [[0x00, 0x00, 0x11, 0x11]; 4];
+            const RAMP_BAYER: [[u8; 4]; 4] = [
+                [0x00, 0x01, 0x01, 0x01],
+                [0x10, 0x10, 0x10, 0x11],
+                [0x01, 0x01, 0x01, 0x01],
+                [0x10, 0x10, 0x11, 0x11],
+            ];
+            assert_eq!(present_golden(&ramp, &mut dither::Nearest), RAMP_NEAREST);
+            assert_eq!(present_golden(&ramp, &mut dither::Bayer4x4), RAMP_BAYER);
+        }
+
+        #[cfg(feature = "dither-fs")]
+        {
+            const FS: [[u8; 4]; 4] = [
+                [0x52, 0x53, 0x13, 0x32],
+                [0x52, 0x52, 0x52, 0x53],
+                [0x65, 0x13, 0x13, 0x21],
+                [0x10, 0x16, 0x36, 0x13],
+            ];
+            // A reused instance must not leak error from the previous frame
+            let mut fs = dither::FloydSteinberg::new(8);
+            assert_eq!(present_golden(&gradient, &mut fs), FS);
+            assert_eq!(present_golden(&gradient, &mut fs), FS);
+        }
+
+        let mut display = new_display();
+        let mut strat = dither::Nearest;
+        assert!(matches!(
+            display.present_rgb(&[[0; 3]; 7], 2, &mut strat),
+            Err(Error::InvalidLength)
+        ));
+        assert!(matches!(
+            display.present_rgb(&[[0; 3]; 4], 0, &mut strat),
+            Err(Error::InvalidLength)
+        ));
+
+        // Sizes are checked against the rotated, logical display
+        let wide = vec![[0; 3]; WIDTH as usize];
+        display.set_rotation(Rotation::Deg90);
+        assert!(matches!(
+            display.present_rgb(&wide, WIDTH, &mut strat),
+            Err(Error::InvalidLength)
+        ));
+        let tall = vec![[0; 3]; WIDTH as usize];
+        assert!(display.present_rgb(&tall, 1, &mut strat).is_ok());
+    }
+
+    #[cfg(all(feature = "overlay", feature = "split-transmission"))]
+    #[test]
+    fn test_previous_frame_includes_overlay() {
+        let mut display = new_display();
+        display.overlay_set_pixel(0, 0, Color::Red);
+        display.set_data_transmission_split(true);
+        assert_eq!(display.previous_frame.as_deref().unwrap()[0], 0x31);
+
+        display.overlay_clear();
+        display.overlay_set_pixel(1, 0, Color::Blue);
+        display.flush().unwrap();
+        // The next old frame is the merged one now on the panel
+        let previous = display.previous_frame.as_deref().unwrap();
+        assert_eq!(previous[0], 0x15);
+        assert_eq!(previous[1..], display.buffer[1..]);
+    }
+
+    #[cfg(feature = "split-transmission")]
+    #[test]
+    fn test_flush_from_iter_keeps_previous_frame() {
+        let mut display = new_display();
+        display.set_data_transmission_split(true);
+        let count = (WIDTH * HEIGHT) as usize;
+        display
+            .flush_from_iter(core::iter::repeat_n(Color::Green, count), count)
+            .unwrap();
+        assert!(display
+            .previous_frame
+            .as_deref()
+            .unwrap()
+            .iter()
+            .all(|&b| b == 0x66));
+
+        // The next flush sends the streamed frame as the old data
+        display.spi.writes.clear();
+        display.flush().unwrap();
+        let writes = &display.spi.writes;
+        let start = writes
+            .iter()
+            .position(|w| w.as_slice() == [CMD_DATA_START_TRANSMISSION])
+            .unwrap();
+        let old: Vec<u8> = writes[start + 1..]
+            .iter()
+            .take_while(|w| w.as_slice() != [CMD_DATA_START_TRANSMISSION2])
+            .flatten()
+            .copied()
+            .collect();
+        assert_eq!(old.len(), BUFFER_SIZE);
+        assert!(old.iter().all(|&b| b == 0x66));
+    }
+
+    #[cfg(feature = "split-transmission")]
+    #[test]
+    fn test_data_transmission_split() {
+        let mut display = new_display();
+        display.set_data_transmission_split(true);
+        display.set_pixel(0, 0, Color::Red);
+        display.flush().unwrap();
+        display.clear_buffer(Color::Blue);
+        display.flush().unwrap();
+
+        let writes = &display.spi.writes;
+        let frames: Vec<(u8, Vec<u8>)> = writes
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| {
+                w.as_slice() == [CMD_DATA_START_TRANSMISSION]
+                    || w.as_slice() == [CMD_DATA_START_TRANSMISSION2]
+            })
+            .map(|(i, w)| {
+                let data = writes[i + 1..]
+                    .iter()
+                    .take_while(|w| w.len() > 1)
+                    .flatten()
+                    .copied()
+                    .collect();
+                (w[0], data)
+            })
+            .collect();
+
+        let mut first = vec![0x11; BUFFER_SIZE];
+        first[0] = 0x31;
+        let expected = [
+            (CMD_DATA_START_TRANSMISSION, vec![0x11; BUFFER_SIZE]),
+            (CMD_DATA_START_TRANSMISSION2, first.clone()),
+            (CMD_DATA_START_TRANSMISSION, first),
+            (CMD_DATA_START_TRANSMISSION2, vec![0x55; BUFFER_SIZE]),
+        ];
+        assert_eq!(frames.len(), expected.len());
+        for (frame, expected) in frames.iter().zip(&expected) {
+            assert!(frame == expected, "frame for command {:#04x}", expected.0);
+        }
+
+        // Disabled: a single transmission per flush
+        display.set_data_transmission_split(false);
+        display.spi.writes.clear();
+        display.flush().unwrap();
+        assert!(!display
+            .spi
+            .writes
+            .iter()
+            .any(|w| w.as_slice() == [CMD_DATA_START_TRANSMISSION2]));
+    }
+
+    #[test]
+    fn test_color_next_wraps() {
+        assert_eq!(Color::Green.next(), Color::Black);
+        assert_eq!(Color::Black.next(), Color::White);
+        for pair in Color::ALL.windows(2) {
+            assert_eq!(pair[0].next(), pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_cycle_buffer() {
+        let mut display = new_display();
+        for (x, &color) in Color::ALL.iter().enumerate() {
+            display.set_pixel(x as u32, 0, color);
+        }
+        display.buffer[BUFFER_SIZE - 1] = 0xF6;
+
+        display.cycle_buffer();
+        for (x, &color) in Color::ALL.iter().enumerate() {
+            assert_eq!(nibble(&display, x as u32, 0), color.next() as u8);
+        }
+        assert_eq!(display.buffer[BUFFER_SIZE - 1], 0x00);
+        assert_eq!(nibble(&display, 10, 10), Color::Yellow as u8);
+
+        // Seven steps bring every valid pixel back to where it started
+        for _ in 1..Color::ALL.len() {
+            display.cycle_buffer();
+        }
+        for (x, &color) in Color::ALL.iter().enumerate() {
+            assert_eq!(nibble(&display, x as u32, 0), color as u8);
+        }
+        assert!(display.buffer[WIDTH as usize..BUFFER_SIZE - 1]
+            .iter()
+            .all(|&b| b == 0x11));
+    }
+
+    #[test]
+    fn test_flush_interlaced_preview() {
+        let mut display = new_display();
+        for (y, color) in [Color::Red, Color::Blue, Color::Green, Color::Black]
+            .into_iter()
+            .enumerate()
+        {
+            display.fill_span(0, WIDTH, y as u32, color);
+        }
+        let buffer = display.buffer.to_vec();
+        display.flush_interlaced_preview().unwrap();
+        assert_eq!(*display.buffer, *buffer);
+
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        let row_bytes = WIDTH as usize / 2;
+        let rows: Vec<&[u8]> = frame.chunks(row_bytes).collect();
+        for (y, row) in rows.iter().enumerate() {
+            let source = y & !1;
+            assert_eq!(*row, &buffer[source * row_bytes..][..row_bytes], "row {y}");
+        }
+        // Odd source rows are never sent
+        assert!(rows.iter().all(|row| row[0] != 0x55 && row[0] != 0x00));
+
+        // Across the whole frame, nothing from an odd row reaches the wire
+        for y in (1..HEIGHT).step_by(2) {
+            display.fill_span(0, WIDTH, y, Color::Blue);
+        }
+        display.spi.writes.clear();
+        display.flush_interlaced_preview().unwrap();
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        assert!(!frame.contains(&0x55));
+    }
+
+    #[test]
+    fn test_debug_region() {
+        use alloc::format;
+
+        let mut display = new_display();
+        display.set_pixel(11, 20, Color::Red);
+        display.set_pixel(10, 21, Color::Blue);
+        display.set_pixel(11, 21, Color::Black);
+
+        let area = Rectangle::new(Point::new(10, 20), Size::new(2, 2));
+        assert_eq!(format!("{:?}", display.debug_region(&area)), "WR\nBK");
+
+        // Clipped to the display
+        let corner = Rectangle::new(Point::new(WIDTH as i32 - 1, -3), Size::new(4, 4));
+        assert_eq!(format!("{:?}", display.debug_region(&corner)), "W");
+    }
+
+    #[test]
+    fn test_color_map() {
+        let mut display = new_display();
+        display.set_pixel(0, 0, Color::Red);
+        display.set_pixel(3, 0, Color::Red);
+        display.set_pixel(4, 0, Color::Blue);
+
+        let mut map = Color::ALL;
+        map[Color::Red as usize] = Color::Orange;
+        display.set_color_map(map);
+        display.flush().unwrap();
+
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame.len(), BUFFER_SIZE);
+        assert_eq!(frame[..3], [0x41, 0x14, 0x51]);
+        assert!(frame[3..].iter().all(|&b| b == 0x11));
+        // The buffer keeps the logical colors
+        assert_eq!(nibble(&display, 0, 0), Color::Red as u8);
+
+        // Back to identity
+        display.set_color_map(Color::ALL);
+        display.spi.writes.clear();
+        display.flush().unwrap();
+        assert_eq!(
+            transmitted_frame(&display.spi.writes)[..3],
+            [0x31, 0x13, 0x51]
+        );
+    }
+
+    #[test]
+    fn test_pre_refresh_delay() {
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        #[derive(Debug, PartialEq)]
+        enum Entry {
+            Write(Vec<u8>),
+            DelayNs(u32),
+        }
+        type Log = Rc<RefCell<Vec<Entry>>>;
+
+        struct LogSpi(Log);
+
+        impl SpiErrorType for LogSpi {
+            type Error = MockError;
+        }
+
+        impl SpiDevice<u8> for LogSpi {
+            fn transaction(&mut self, operations: &mut [Operation<u8>]) -> Result<(), MockError> {
+                for op in operations {
+                    if let Operation::Write(data) = op {
+                        self.0.borrow_mut().push(Entry::Write(data.to_vec()));
+                    }
+                }
+                Ok(())
             }
         }
 
-        Ok(())
+        struct LogDelay(Log);
+
+        impl DelayNs for LogDelay {
+            fn delay_ns(&mut self, ns: u32) {
+                self.0.borrow_mut().push(Entry::DelayNs(ns));
+            }
+        }
+
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let mut display = Gdep073e01::new(
+            LogSpi(log.clone()),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            LogDelay(log.clone()),
+        );
+        display.flush().unwrap();
+        assert!(!log.borrow().iter().any(|e| matches!(e, Entry::DelayNs(_))));
+
+        log.borrow_mut().clear();
+        display.set_pre_refresh_delay_ms(25);
+        display.flush().unwrap();
+        let log = log.borrow();
+        let refresh = log
+            .iter()
+            .position(|e| *e == Entry::Write(vec![CMD_DISPLAY_REFRESH]))
+            .unwrap();
+        // Last frame bytes, then the delay, then the refresh command
+        assert!(matches!(log[refresh - 2], Entry::Write(ref data) if data.len() > 1));
+        assert_eq!(log[refresh - 1], Entry::DelayNs(25_000_000));
+        assert_eq!(
+            log.iter()
+                .filter(|e| matches!(e, Entry::DelayNs(_)))
+                .count(),
+            1
+        );
     }
 
-    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
-        self.clear_buffer(color);
-        Ok(())
+    #[test]
+    fn test_sleep_on_drop() {
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+
+        struct SharedSpi(Rc<RefCell<Vec<Vec<u8>>>>);
+
+        impl SpiErrorType for SharedSpi {
+            type Error = MockError;
+        }
+
+        impl SpiDevice<u8> for SharedSpi {
+            fn transaction(&mut self, operations: &mut [Operation<u8>]) -> Result<(), MockError> {
+                for op in operations {
+                    if let Operation::Write(data) = op {
+                        self.0.borrow_mut().push(data.to_vec());
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let writes = Rc::new(RefCell::new(Vec::new()));
+        let new_display = || {
+            Gdep073e01::new(
+                SharedSpi(writes.clone()),
+                MockPin::default(),
+                MockPin::default(),
+                MockPin::default(),
+                MockPin::default(),
+                MockDelay::default(),
+            )
+        };
+
+        drop(new_display());
+        assert!(writes.borrow().is_empty());
+
+        let mut display = new_display();
+        display.set_sleep_on_drop(true);
+        drop(display);
+        assert_eq!(
+            *writes.borrow(),
+            [
+                vec![CMD_POWER_OFF],
+                vec![0x00],
+                vec![CMD_DEEP_SLEEP],
+                vec![0xA5]
+            ]
+        );
     }
-}
 
-impl<SPI, CS, DC, RST, BUSY, DELAY> OriginDimensions for Gdep073e01<SPI, CS, DC, RST, BUSY, DELAY> {
-    fn size(&self) -> Size {
-        Size::new(WIDTH, HEIGHT)
+    #[test]
+    fn test_flush_flipped() {
+        let mut display = new_display();
+        display.set_pixel(0, 0, Color::Red);
+        display.set_pixel(3, 1, Color::Blue);
+        display.set_pixel(WIDTH - 2, HEIGHT - 1, Color::Green);
+        let buffer = display.buffer.to_vec();
+        display.flush_flipped().unwrap();
+        assert_eq!(*display.buffer, *buffer);
+
+        let reference: Vec<u8> = buffer.iter().rev().map(|&b| b.rotate_left(4)).collect();
+        let frame = transmitted_frame(&display.spi.writes);
+        assert_eq!(frame, reference);
+        // Top-left pixel lands bottom-right and vice versa
+        assert_eq!(frame[BUFFER_SIZE - 1], 0x13);
+        assert_eq!(frame[0], 0x16);
     }
-}
 
-/// Prelude module for convenient imports.
-pub mod prelude {
-    pub use crate::{Color, Error, Gdep073e01, HEIGHT, WIDTH};
-    pub use embedded_graphics::prelude::*;
-    pub use embedded_hal::{
-        delay::DelayNs,
-        digital::{ErrorType as DigitalErrorType, InputPin, OutputPin},
-        spi::{ErrorType as SpiErrorType, SpiDevice},
-    };
-}
+    #[test]
+    fn test_to_rgb565() {
+        let expected = [
+            (Color::Black, Rgb565::new(0, 0, 0)),
+            (Color::White, Rgb565::new(31, 63, 31)),
+            (Color::Yellow, Rgb565::new(31, 63, 0)),
+            (Color::Red, Rgb565::new(31, 0, 0)),
+            (Color::Orange, Rgb565::new(31, 32, 0)),
+            (Color::Blue, Rgb565::new(0, 0, 31)),
+            (Color::Green, Rgb565::new(0, 63, 0)),
+        ];
+        for (color, rgb) in expected {
+            assert_eq!(color.to_rgb565(), rgb, "{color:?}");
+        }
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloc::vec::Vec;
-    use embedded_hal::digital::{ErrorType as DigitalErrorType, PinState};
-    use embedded_hal::spi::{ErrorType as SpiErrorType, Operation};
+    #[test]
+    fn test_draw_mono_text() {
+        use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyleBuilder};
+        use embedded_graphics::text::{Baseline, Text};
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-    struct MockError;
+        for bg in [None, Some(Color::Yellow)] {
+            let mut display = new_display();
+            let mut reference = new_display();
+            for d in [&mut display, &mut reference] {
+                d.fill_solid(
+                    &Rectangle::new(Point::new(0, 0), Size::new(40, 30)),
+                    Color::Red,
+                )
+                .unwrap();
+            }
 
-    impl embedded_hal::digital::Error for MockError {
-        fn kind(&self) -> embedded_hal::digital::ErrorKind {
-            embedded_hal::digital::ErrorKind::Other
+            let next =
+                display.draw_mono_text("Hi\nA", &FONT_6X10, Point::new(3, 4), Color::Blue, bg);
+            assert_eq!(next, Point::new(9, 14));
+
+            let mut style = MonoTextStyleBuilder::new()
+                .font(&FONT_6X10)
+                .text_color(Color::Blue);
+            if let Some(bg) = bg {
+                style = style.background_color(bg);
+            }
+            Text::with_baseline("Hi\nA", Point::new(3, 4), style.build(), Baseline::Top)
+                .draw(&mut reference)
+                .unwrap();
+            assert_eq!(*display.buffer, *reference.buffer);
+
+            // Sample of 'H': both stems, the bar, and the clear gap above it
+            for (x, y) in [(3, 5), (3, 11), (7, 5), (5, 8), (7, 11), (5, 15)] {
+                assert_eq!(display.get_pixel(x, y), Some(Color::Blue), "({x}, {y})");
+            }
+            let gap = display.get_pixel(5, 6);
+            assert_eq!(gap, Some(bg.unwrap_or(Color::Red)));
         }
     }
 
-    impl embedded_hal::spi::Error for MockError {
-        fn kind(&self) -> embedded_hal::spi::ErrorKind {
-            embedded_hal::spi::ErrorKind::Other
+    #[test]
+    fn test_draw_iter_batches_bytes() {
+        use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+        use embedded_graphics::text::Text;
+
+        /// Target recording the pixels text rendering produces.
+        struct Recorder(Vec<Pixel<Color>>);
+
+        impl OriginDimensions for Recorder {
+            fn size(&self) -> Size {
+                Size::new(WIDTH, HEIGHT)
+            }
         }
-    }
 
-    #[derive(Debug, Default)]
-    struct MockSpi {
-        pub writes: Vec<Vec<u8>>,
+        impl DrawTarget for Recorder {
+            type Color = Color;
+            type Error = core::convert::Infallible;
+            fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+            where
+                I: IntoIterator<Item = Pixel<Color>>,
+            {
+                self.0.extend(pixels);
+                Ok(())
+            }
+        }
+
+        let mut recorder = Recorder(Vec::new());
+        let style = MonoTextStyle::new(&FONT_6X10, Color::Black);
+        Text::new("Hello, e-paper", Point::new(-3, 20), style)
+            .draw(&mut recorder)
+            .unwrap();
+        let pixels = recorder.0;
+
+        let mut reference = new_display();
+        for &Pixel(p, color) in &pixels {
+            if p.x >= 0 && p.y >= 0 {
+                reference.set_pixel(p.x as u32, p.y as u32, color);
+            }
+        }
+
+        let mut counting = Counting::new();
+        write_pixels(&mut counting, pixels.iter().copied());
+        assert_eq!(counting.bytes[..], reference.buffer[..]);
+
+        // One read and one write per run of pixels sharing a byte
+        let visible: Vec<usize> = pixels
+            .iter()
+            .filter(|Pixel(p, _)| p.x >= 0 && p.y >= 0)
+            .map(|Pixel(p, _)| (p.y as usize * WIDTH as usize + p.x as usize) / 2)
+            .collect();
+        let runs = 1 + visible.windows(2).filter(|w| w[0] != w[1]).count();
+        assert_eq!(counting.reads.get(), runs);
+        assert_eq!(counting.writes, runs);
+        assert!(runs < visible.len());
+
+        let mut display = new_display();
+        display.draw_iter(pixels).unwrap();
+        assert_eq!(*display.buffer, *reference.buffer);
     }
 
-    impl SpiErrorType for MockSpi {
-        type Error = MockError;
+    #[test]
+    fn test_color_display() {
+        use alloc::format;
+
+        assert_eq!(format!("{}", Color::Orange), "Orange");
+        assert_eq!(format!("{:>6}", Color::Red), "   Red");
+        for color in Color::ALL {
+            assert_eq!(format!("{color}"), format!("{color:?}"));
+        }
     }
 
-    impl SpiDevice<u8> for MockSpi {
-        fn transaction(&mut self, operations: &mut [Operation<u8>]) -> Result<(), Self::Error> {
-            for op in operations {
-                if let Operation::Write(data) = op {
-                    self.writes.push(data.to_vec());
+    #[test]
+    fn test_flood_fill() {
+        let mut display = new_display();
+        // Red outline from (2, 1) to (9, 6)
+        let outline = Rectangle::new(Point::new(2, 1), Size::new(8, 6));
+        for p in outline.points() {
+            let edge = p.x == 2 || p.x == 9 || p.y == 1 || p.y == 6;
+            if edge {
+                display.set_pixel(p.x as u32, p.y as u32, Color::Red);
+            }
+        }
+        // An obstacle inside the region keeps its color
+        display.set_pixel(5, 3, Color::Red);
+        let before = display.buffer.to_vec();
+
+        display.flood_fill(Point::new(4, 4), Color::Green);
+        for p in Rectangle::new(Point::zero(), Size::new(16, 10)).points() {
+            let inside = p.x > 2 && p.x < 9 && p.y > 1 && p.y < 6 && p != Point::new(5, 3);
+            let expected = if inside {
+                Color::Green as u8
+            } else {
+                let i = (p.y as u32 * WIDTH + p.x as u32) as usize / 2;
+                if p.x & 1 == 0 {
+                    before[i] >> 4
+                } else {
+                    before[i] & 0x0F
                 }
+            };
+            assert_eq!(display.nibble_at(p), Some(expected), "{p:?}");
+        }
+        // Nothing outside the 16x10 corner changed
+        let changed = display
+            .buffer
+            .iter()
+            .zip(&before)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(changed, 4 * 4);
+
+        // Seed already the new color, or off-screen: no-op
+        let after = display.buffer.to_vec();
+        display.flood_fill(Point::new(4, 4), Color::Green);
+        display.flood_fill(Point::new(-1, 4), Color::Blue);
+        display.flood_fill(Point::new(0, HEIGHT as i32), Color::Blue);
+        assert_eq!(*display.buffer, *after);
+
+        // The outside region is the rest of the screen
+        display.flood_fill(Point::new(0, 0), Color::Black);
+        assert_eq!(
+            display.nibble_at(Point::new(WIDTH as i32 - 1, HEIGHT as i32 - 1)),
+            Some(0)
+        );
+        assert_eq!(display.nibble_at(Point::new(2, 1)), Some(Color::Red as u8));
+        assert_eq!(
+            display.nibble_at(Point::new(4, 4)),
+            Some(Color::Green as u8)
+        );
+    }
+
+    #[test]
+    fn test_rotation() {
+        fn nibble(
+            display: &Gdep073e01<'_, MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay>,
+            x: u32,
+            y: u32,
+        ) -> u8 {
+            display.nibble_at(Point::new(x as i32, y as i32)).unwrap()
+        }
+
+        let mut display = new_display();
+        display.set_rotation(Rotation::Deg90);
+        assert_eq!(display.size(), Size::new(HEIGHT, WIDTH));
+
+        // Logical (0, 0) is the physical top-right corner, byte 399 low nibble
+        display.set_pixel(0, 0, Color::Red);
+        assert_eq!(display.buffer[WIDTH as usize / 2 - 1], 0x13);
+        display.set_pixel(2, 5, Color::Blue);
+        assert_eq!(nibble(&display, WIDTH - 6, 2), Color::Blue as u8);
+        // Clipped against the logical size
+        display.set_pixel(HEIGHT, 0, Color::Black);
+        display.set_pixel(0, HEIGHT, Color::Green);
+        assert_eq!(nibble(&display, WIDTH - 1 - HEIGHT, 0), Color::Green as u8);
+
+        // Drawing through embedded-graphics follows the same mapping
+        for (rotation, physical) in [
+            (Rotation::Deg0, (3, 1)),
+            (Rotation::Deg90, (WIDTH - 2, 3)),
+            (Rotation::Deg180, (WIDTH - 4, HEIGHT - 2)),
+            (Rotation::Deg270, (1, HEIGHT - 4)),
+        ] {
+            let mut display = new_display();
+            display.set_rotation(rotation);
+            Pixel(Point::new(3, 1), Color::Yellow)
+                .draw(&mut display)
+                .unwrap();
+            assert_eq!(
+                nibble(&display, physical.0, physical.1),
+                Color::Yellow as u8
+            );
+
+            let rect = Rectangle::new(Point::new(3, 1), Size::new(4, 2));
+            let mut expected = new_display();
+            for p in rect.points() {
+                let (x, y) = rotation.to_physical(p.x as u32, p.y as u32);
+                expected.set_pixel(x, y, Color::Orange);
             }
-            Ok(())
+            display.clear_buffer(Color::White);
+            display.fill_solid(&rect, Color::Orange).unwrap();
+            assert_eq!(*display.buffer, *expected.buffer, "{rotation:?}");
+            display.clear_buffer(Color::White);
+            display
+                .fill_contiguous(&rect, core::iter::repeat(Color::Orange))
+                .unwrap();
+            assert_eq!(*display.buffer, *expected.buffer, "{rotation:?}");
         }
+    }
 
-        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
-            self.writes.push(words.to_vec());
-            Ok(())
+    #[test]
+    fn test_mirror() {
+        let gradient = |display: &mut Gdep073e01<'_, _, _, _, _, _, _>| {
+            for (i, color) in Color::ALL.into_iter().enumerate() {
+                display.set_pixel(i as u32, 0, color);
+                display.set_pixel(0, i as u32 + 1, color);
+            }
+        };
+        let row: Vec<u8> = Color::ALL.iter().map(|&c| c as u8).collect();
+        let last = (WIDTH - 1) as i32;
+        let bottom = (HEIGHT - 1) as i32;
+
+        let mut display = new_display();
+        display.set_mirror(true, false);
+        gradient(&mut display);
+        for (i, &c) in row.iter().enumerate() {
+            assert_eq!(display.nibble_at(Point::new(last - i as i32, 0)), Some(c));
+            assert_eq!(display.nibble_at(Point::new(last, i as i32 + 1)), Some(c));
+        }
+
+        let mut display = new_display();
+        display.set_mirror(false, true);
+        gradient(&mut display);
+        for (i, &c) in row.iter().enumerate() {
+            assert_eq!(display.nibble_at(Point::new(i as i32, bottom)), Some(c));
+            assert_eq!(
+                display.nibble_at(Point::new(0, bottom - 1 - i as i32)),
+                Some(c)
+            );
         }
+
+        // Both axes equal a 180° rotation, through every drawing path
+        let mut mirrored = new_display();
+        mirrored.set_mirror(true, true);
+        let mut rotated = new_display();
+        rotated.set_rotation(Rotation::Deg180);
+        for display in [&mut mirrored, &mut rotated] {
+            gradient(display);
+            let rect = Rectangle::new(Point::new(10, 4), Size::new(5, 3));
+            display.fill_solid(&rect, Color::Blue).unwrap();
+            Pixel(Point::new(30, 9), Color::Green)
+                .draw(display)
+                .unwrap();
+            display.flood_fill(Point::new(40, 40), Color::Yellow);
+            display.flood_fill(Point::new(12, 5), Color::Red);
+        }
+        assert_eq!(*mirrored.buffer, *rotated.buffer);
+
+        // Mirroring happens in logical space, before the rotation
+        let mut display = new_display();
+        display.set_rotation(Rotation::Deg90);
+        display.set_mirror(true, false);
+        display.set_pixel(0, 0, Color::Red);
+        assert_eq!(
+            display.nibble_at(Point::new(last, HEIGHT as i32 - 1)),
+            Some(Color::Red as u8)
+        );
     }
 
-    #[derive(Debug, Default)]
-    struct MockPin {
-        pub states: Vec<PinState>,
+    #[test]
+    fn test_last_flush_stats() {
+        let mut display = new_display();
+        assert_eq!(display.last_flush_stats(), None);
+
+        display.busy.high_reads = 3;
+        display.flush().unwrap();
+        assert_eq!(
+            display.last_flush_stats(),
+            Some(FlushStats {
+                bytes: BUFFER_SIZE,
+                partial: false,
+                refresh_ms_est: 3 * BUSY_WAIT_DELAY_MS,
+            })
+        );
+
+        display
+            .flush_partial(Rectangle::new(Point::new(0, 0), Size::new(8, 2)))
+            .unwrap();
+        assert_eq!(
+            display.last_flush_stats(),
+            Some(FlushStats {
+                bytes: 8,
+                partial: true,
+                refresh_ms_est: 0,
+            })
+        );
+
+        // A failed refresh keeps the previous stats
+        display.busy.high_reads = usize::MAX;
+        display.set_busy_timeout_ms(20);
+        assert!(display.flush().is_err());
+        assert!(display.last_flush_stats().unwrap().partial);
     }
 
-    impl DigitalErrorType for MockPin {
-        type Error = MockError;
+    #[test]
+    fn test_take_dirty() {
+        let mut display = new_display();
+        assert_eq!(display.take_dirty(), None);
+
+        display.set_pixel(10, 20, Color::Red);
+        display.set_pixel(30, 5, Color::Blue);
+        assert_eq!(
+            display.take_dirty(),
+            Some(Rectangle::with_corners(
+                Point::new(10, 5),
+                Point::new(30, 20)
+            ))
+        );
+        assert_eq!(display.take_dirty(), None);
+
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(-2, 4), Size::new(4, 2)),
+                Color::Green,
+            )
+            .unwrap();
+        display.mark_dirty(&Rectangle::new(
+            Point::new(WIDTH as i32 - 1, 7),
+            Size::new(5, 1),
+        ));
+        display.mark_dirty(&Rectangle::new(Point::new(50, 50), Size::zero()));
+        assert_eq!(
+            display.take_dirty(),
+            Some(Rectangle::with_corners(
+                Point::new(0, 4),
+                Point::new(WIDTH as i32 - 1, 7)
+            ))
+        );
     }
 
-    impl OutputPin for MockPin {
-        fn set_low(&mut self) -> Result<(), Self::Error> {
-            self.states.push(PinState::Low);
-            Ok(())
+    #[test]
+    fn test_flush_dirty() {
+        let mut display = new_display();
+        display.flush_dirty().unwrap();
+        assert!(display.spi.writes.is_empty());
+
+        display.set_pixel(10, 5, Color::Red);
+        display
+            .fill_solid(
+                &Rectangle::new(Point::new(100, 50), Size::new(4, 3)),
+                Color::Blue,
+            )
+            .unwrap();
+        Pixel(Point::new(60, 70), Color::Green)
+            .draw(&mut display)
+            .unwrap();
+        display.flush_dirty().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PARTIAL_WINDOW),
+            [0x00, 10, 0x00, 103, 0x00, 5, 0x00, 70, 0x01]
+        );
+
+        // Emptied by the flush
+        display.spi.writes.clear();
+        display.flush_dirty().unwrap();
+        assert!(display.spi.writes.is_empty());
+
+        // Clearing marks the whole display
+        display.clear_buffer(Color::White);
+        display.flush_dirty().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PARTIAL_WINDOW),
+            [0x00, 0x00, 0x03, 0x1F, 0x00, 0x00, 0x01, 0xDF, 0x01]
+        );
+
+        // A full flush empties it too
+        display.set_pixel(1, 1, Color::Black);
+        display.flush().unwrap();
+        display.spi.writes.clear();
+        display.flush_dirty().unwrap();
+        assert!(display.spi.writes.is_empty());
+
+        // Tracked in physical coordinates
+        display.set_rotation(Rotation::Deg180);
+        display.set_pixel(0, 0, Color::Black);
+        display.flush_dirty().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PARTIAL_WINDOW),
+            [0x03, 0x1E, 0x03, 0x1F, 0x01, 0xDF, 0x01, 0xDF, 0x01]
+        );
+    }
+
+    #[test]
+    fn test_flush_partial() {
+        let mut display = new_display();
+        for p in Rectangle::new(Point::new(4, 2), Size::new(10, 3)).points() {
+            display.set_pixel(p.x as u32, p.y as u32, Color::Green);
         }
 
-        fn set_high(&mut self) -> Result<(), Self::Error> {
-            self.states.push(PinState::High);
-            Ok(())
+        // Odd bounds are widened to whole bytes: x 3..=8 becomes 2..=9
+        display
+            .flush_partial(Rectangle::new(Point::new(3, 2), Size::new(6, 3)))
+            .unwrap();
+        let writes = &display.spi.writes;
+        assert_eq!(writes[0], [CMD_PARTIAL_IN]);
+        assert_eq!(
+            command_data(writes, CMD_PARTIAL_WINDOW),
+            [0x00, 2, 0x00, 9, 0x00, 2, 0x00, 4, 0x01]
+        );
+        let start = writes
+            .iter()
+            .position(|w| w.as_slice() == [CMD_DATA_START_TRANSMISSION])
+            .unwrap();
+        assert_eq!(&writes[start + 1..start + 4], [[0x11, 0x66, 0x66, 0x66]; 3]);
+        assert_eq!(writes[start + 4], [CMD_DISPLAY_REFRESH]);
+        assert_eq!(writes.last().unwrap(), &[CMD_PARTIAL_OUT]);
+
+        // Clamped to the display
+        display.spi.writes.clear();
+        display
+            .flush_partial(Rectangle::new(Point::new(797, 478), Size::new(10, 10)))
+            .unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PARTIAL_WINDOW),
+            [0x03, 0x1C, 0x03, 0x1F, 0x01, 0xDE, 0x01, 0xDF, 0x01]
+        );
+
+        // Nothing visible: nothing sent
+        display.spi.writes.clear();
+        display
+            .flush_partial(Rectangle::new(Point::new(-20, 5), Size::new(10, 10)))
+            .unwrap();
+        display
+            .flush_partial(Rectangle::new(Point::new(5, 5), Size::zero()))
+            .unwrap();
+        assert!(display.spi.writes.is_empty());
+    }
+
+    #[test]
+    fn test_flush_delta() {
+        let mut display = new_display();
+        display.set_pixel(10, 10, Color::Red);
+        display.flush_delta().unwrap();
+        assert_eq!(transmitted_frame(&display.spi.writes).len(), BUFFER_SIZE);
+
+        // Move the pixel and change one far away: one window over both, one refresh
+        display.spi.writes.clear();
+        display.set_pixel(10, 10, Color::White);
+        display.set_pixel(12, 10, Color::Red);
+        display.set_pixel(301, 50, Color::Blue);
+        display.flush_delta().unwrap();
+
+        let writes = &display.spi.writes;
+        assert_eq!(writes[0], [CMD_PARTIAL_IN]);
+        let count = |command: u8| writes.iter().filter(|w| w.as_slice() == [command]).count();
+        assert_eq!(count(CMD_PARTIAL_WINDOW), 1);
+        assert_eq!(count(CMD_DATA_START_TRANSMISSION), 1);
+        assert_eq!(count(CMD_DISPLAY_REFRESH), 1);
+        assert_eq!(
+            command_data(writes, CMD_PARTIAL_WINDOW),
+            [0x00, 10, 0x01, 45, 0x00, 10, 0x00, 50, 0x01]
+        );
+        let expected: Vec<u8> = (10..=50)
+            .flat_map(|y| display.buffer[y * 400 + 5..y * 400 + 151].iter().copied())
+            .collect();
+        assert_eq!(transmitted_frame(writes), expected.as_slice());
+        assert_eq!(writes.last().unwrap(), &[CMD_PARTIAL_OUT]);
+
+        // Nothing changed: nothing sent
+        display.spi.writes.clear();
+        display.flush_delta().unwrap();
+        assert!(display.spi.writes.is_empty());
+
+        // Over the threshold: full flush
+        display.set_delta_threshold(1);
+        display.set_pixel(0, 0, Color::Blue);
+        display.set_pixel(5, 5, Color::Blue);
+        display.flush_delta().unwrap();
+        assert!(!display.spi.writes.contains(&vec![CMD_PARTIAL_IN]));
+        assert_eq!(transmitted_frame(&display.spi.writes), &display.buffer[..]);
+    }
+
+    #[test]
+    fn test_init_step() {
+        let mut expected = new_display();
+        expected.set_border_color(Color::Blue).unwrap();
+        expected.spi.writes.clear();
+        expected.busy.high_reads = 2;
+        expected.init().unwrap();
+
+        let mut display = new_display();
+        display.set_border_color(Color::Blue).unwrap();
+        display.spi.writes.clear();
+        display.busy.high_reads = 2;
+        let mut progress = Vec::new();
+        loop {
+            let step = display.init_step().unwrap();
+            progress.push(step);
+            if step == InitProgress::Done {
+                break;
+            }
         }
+
+        // Two reset edges and 13 commands, then power-on and two busy polls
+        use InitProgress::*;
+        let mut want = vec![InProgress; 15];
+        want.extend([PoweringOn, PoweringOn, PoweringOn, Done]);
+        assert_eq!(progress, want);
+        assert_eq!(display.spi.writes, expected.spi.writes);
+        assert_eq!(display.rst.states, expected.rst.states);
+        assert_eq!(display.last_init_busy_polls(), Some(2));
+        assert_eq!(display.init_state, InitState::Idle);
     }
 
-    impl InputPin for MockPin {
-        fn is_high(&mut self) -> Result<bool, Self::Error> {
-            Ok(false) // Always return not busy for tests
+    #[test]
+    fn test_init_busy_polls() {
+        let mut display = new_display();
+        assert_eq!(display.last_init_busy_polls(), None);
+        display.init().unwrap();
+        assert_eq!(display.last_init_busy_polls(), Some(0));
+
+        // Only the power-on wait is recorded
+        display.busy.high_reads = 5;
+        display.init().unwrap();
+        assert_eq!(display.last_init_busy_polls(), Some(5));
+        display.busy.high_reads = 2;
+        display.flush().unwrap();
+        assert_eq!(display.last_init_busy_polls(), Some(5));
+    }
+
+    #[test]
+    fn test_draw_line_aa() {
+        let set_pixels = |display: &Gdep073e01<'_, _, _, _, _, _, _>| {
+            let mut set = Vec::new();
+            for y in 0..6 {
+                for x in 0..10 {
+                    if nibble(display, x, y) == Color::Black as u8 {
+                        set.push((x, y));
+                    }
+                }
+            }
+            set
+        };
+
+        // Coverage along (0,0)->(8,3): x=4 splits 127/128 between rows 1 and 2
+        let mut display = new_display();
+        display.draw_line_aa(
+            Point::new(0, 0),
+            Point::new(8, 3),
+            Color::Black,
+            CoverageThreshold::Fixed(127),
+        );
+        let mut set = set_pixels(&display);
+        set.sort_unstable();
+        assert_eq!(
+            set,
+            [
+                (0, 0),
+                (1, 0),
+                (2, 1),
+                (3, 1),
+                (4, 2),
+                (5, 2),
+                (6, 2),
+                (7, 3),
+                (8, 3)
+            ]
+        );
+
+        // Reversed endpoints give the same pixels
+        let mut reversed = new_display();
+        reversed.draw_line_aa(
+            Point::new(8, 3),
+            Point::new(0, 0),
+            Color::Black,
+            CoverageThreshold::Fixed(127),
+        );
+        assert_eq!(*reversed.buffer, *display.buffer);
+
+        // A zero threshold plots every partially covered pixel too
+        let mut display = new_display();
+        display.draw_line_aa(
+            Point::new(0, 0),
+            Point::new(8, 3),
+            Color::Black,
+            CoverageThreshold::Fixed(0),
+        );
+        assert_eq!(set_pixels(&display).len(), 16);
+
+        // Clipping keeps the pixels of the full walk: compare with the same
+        // line shifted fully on-screen
+        let threshold = CoverageThreshold::Fixed(127);
+        let mut clipped = new_display();
+        clipped.draw_line_aa(
+            Point::new(-8, -3),
+            Point::new(9, 4),
+            Color::Black,
+            threshold,
+        );
+        let mut shifted = new_display();
+        shifted.draw_line_aa(
+            Point::new(12, 7),
+            Point::new(29, 14),
+            Color::Black,
+            threshold,
+        );
+        for y in 0..5 {
+            for x in 0..10 {
+                assert_eq!(
+                    clipped.get_pixel(x, y),
+                    shifted.get_pixel(x + 20, y + 10),
+                    "({x}, {y})"
+                );
+            }
         }
 
-        fn is_low(&mut self) -> Result<bool, Self::Error> {
-            Ok(true)
+        // Far-apart endpoints neither overflow nor walk off-screen points
+        let mut display = new_display();
+        display.draw_line_aa(
+            Point::new(i32::MIN, 3),
+            Point::new(i32::MAX, 3),
+            Color::Green,
+            threshold,
+        );
+        let row = WIDTH as usize / 2;
+        assert!(display.buffer[3 * row..4 * row].iter().all(|&b| b == 0x66));
+        display.draw_line_aa(
+            Point::new(i32::MIN, i32::MAX),
+            Point::new(i32::MAX, i32::MIN),
+            Color::Red,
+            threshold,
+        );
+    }
+
+    #[cfg(feature = "pal-spectra6")]
+    #[test]
+    fn test_present_rgb_ordered_streams_packed_rows() {
+        use crate::palette::{add_bias, map_rgb_to_spectra6_nearest};
+
+        const MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+        let (w, h) = (5u32, 3u32);
+        let src: Vec<[u8; 3]> = (0..w * h)
+            .map(|i| [(i * 17) as u8, 128, (255 - i * 9) as u8])
+            .collect();
+
+        let mut reference = new_display();
+        for (i, &rgb) in src.iter().enumerate() {
+            let (x, y) = (i as u32 % w, i as u32 / w);
+            let bias = MATRIX[(y & 3) as usize][(x & 3) as usize] as i16 - 8;
+            let color = map_rgb_to_spectra6_nearest(add_bias(rgb, [bias; 3])).to_driver_color();
+            reference.set_pixel(x, y, color);
         }
+
+        let (mut spi, mut cs, mut dc, mut rst, mut busy) = (
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+        );
+        let mut delay = MockDelay::default();
+        stream::present_rgb_ordered(
+            &mut spi, &mut cs, &mut dc, &mut rst, &mut busy, &mut delay, w, h, &src, &MATRIX,
+        )
+        .unwrap();
+
+        assert_eq!(transmitted_frame(&spi.writes), &reference.buffer[..]);
+        assert_eq!(
+            command_data(&spi.writes, CMD_BOOSTER_SOFT_START1),
+            [0x40, 0x1F, 0x1F, 0x2C]
+        );
+        assert_eq!(
+            spi.writes[spi.writes.len() - 2..],
+            [vec![CMD_DEEP_SLEEP], vec![0xA5]]
+        );
+
+        let mut spi = MockSpi::default();
+        assert!(matches!(
+            stream::present_rgb_ordered(
+                &mut spi,
+                &mut cs,
+                &mut dc,
+                &mut rst,
+                &mut busy,
+                &mut delay,
+                w,
+                h,
+                &src[1..],
+                &MATRIX,
+            ),
+            Err(Error::InvalidLength)
+        ));
+        assert!(spi.writes.is_empty());
+
+        // The BUSY timeout and acknowledgement follow the board config
+        let config = stream::StreamConfig {
+            busy_timeout_ms: 25,
+            ..Default::default()
+        };
+        busy.high_reads = usize::MAX;
+        delay.delays_ns.clear();
+        assert!(matches!(
+            stream::present_rgb_ordered_with(
+                &mut spi, &mut cs, &mut dc, &mut rst, &mut busy, &mut delay, w, h, &src, &MATRIX,
+                &config,
+            ),
+            Err(Error::Timeout)
+        ));
+        assert_eq!(delay.delays_ns[2..], [10_000_000, 10_000_000, 5_000_000]);
+
+        let config = stream::StreamConfig {
+            busy_ack_timeout_ms: Some(5),
+            ..Default::default()
+        };
+        busy.high_reads = 0;
+        spi.writes.clear();
+        assert!(matches!(
+            stream::present_rgb_ordered_with(
+                &mut spi, &mut cs, &mut dc, &mut rst, &mut busy, &mut delay, w, h, &src, &MATRIX,
+                &config,
+            ),
+            Err(Error::BusyNeverAsserted)
+        ));
+        assert_eq!(
+            spi.writes[spi.writes.len() - 2..],
+            [vec![CMD_DISPLAY_REFRESH], vec![0x00]]
+        );
     }
 
-    #[derive(Debug, Default)]
-    struct MockDelay;
+    #[test]
+    fn test_copy_region() {
+        // 4x2 block at (1, 1): distinct colors so misalignment shows
+        let block = [
+            [Color::Red, Color::Blue, Color::Green, Color::Yellow],
+            [Color::Black, Color::Orange, Color::Red, Color::Blue],
+        ];
+        let mut display = new_display();
+        for (y, row) in block.iter().enumerate() {
+            for (x, &color) in row.iter().enumerate() {
+                display.set_pixel(1 + x as u32, 1 + y as u32, color);
+            }
+        }
 
-    impl DelayNs for MockDelay {
-        fn delay_ns(&mut self, _ns: u32) {}
+        // Overlapping move right by 3 (odd): lands at x 4..8
+        let src = Rectangle::new(Point::new(1, 1), Size::new(4, 2));
+        display.copy_region(&src, Point::new(4, 1));
+        for (y, row) in block.iter().enumerate() {
+            for (x, &color) in row.iter().enumerate() {
+                assert_eq!(nibble(&display, 4 + x as u32, 1 + y as u32), color as u8);
+            }
+            // Vacated pixels keep their old content
+            for x in 0..3 {
+                assert_eq!(nibble(&display, 1 + x, 1 + y as u32), row[x as usize] as u8);
+            }
+        }
+        assert_eq!(nibble(&display, 8, 1), Color::White as u8);
+
+        // Overlapping move up-left, then partly off-screen
+        display.copy_region(
+            &Rectangle::new(Point::new(4, 1), Size::new(4, 2)),
+            Point::new(3, 0),
+        );
+        for (x, &color) in block[1].iter().enumerate() {
+            assert_eq!(nibble(&display, 3 + x as u32, 1), color as u8);
+        }
+        display.copy_region(
+            &Rectangle::new(Point::new(3, 0), Size::new(4, 2)),
+            Point::new(-2, -1),
+        );
+        assert_eq!(nibble(&display, 0, 0), block[1][2] as u8);
+        assert_eq!(nibble(&display, 1, 0), block[1][3] as u8);
+
+        // Rotated and mirrored: source and destination are both logical
+        for (rotation, mirror) in [
+            (Rotation::Deg90, (false, false)),
+            (Rotation::Deg270, (true, false)),
+            (Rotation::Deg0, (false, true)),
+        ] {
+            let mut display = new_display();
+            display.set_rotation(rotation);
+            display.set_mirror(mirror.0, mirror.1);
+            let y0 = display.size().height - 4;
+            for (y, row) in block.iter().enumerate() {
+                for (x, &color) in row.iter().enumerate() {
+                    display.set_pixel(1 + x as u32, y0 + y as u32, color);
+                }
+            }
+            let src = Rectangle::new(Point::new(1, y0 as i32), Size::new(4, 2));
+            display.copy_region(&src, Point::new(6, y0 as i32 - 3));
+            for (y, row) in block.iter().enumerate() {
+                for (x, &color) in row.iter().enumerate() {
+                    let (x, y) = (6 + x as u32, y0 - 3 + y as u32);
+                    assert_eq!(
+                        display.get_pixel(x, y),
+                        Some(color),
+                        "{rotation:?} {mirror:?}"
+                    );
+                }
+            }
+        }
     }
 
     #[test]
-    fn test_set_pixel() {
-        let spi = MockSpi::default();
-        let cs = MockPin::default();
-        let dc = MockPin::default();
-        let rst = MockPin::default();
-        let busy = MockPin::default();
-        let delay = MockDelay;
+    fn test_new_with_buffer() {
+        // Any borrow will do, not only a `'static` one
+        let mut storage = vec![0u8; BUFFER_SIZE];
+        let storage_ptr = storage.as_ptr();
+        let mut display = Gdep073e01::new_with_buffer(
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockDelay::default(),
+            storage.as_mut_slice().try_into().unwrap(),
+        );
+        assert_eq!(display.buffer.as_ptr(), storage_ptr);
+        assert_eq!(*display.buffer, *new_display().buffer);
 
-        let mut display = Gdep073e01::new(spi, cs, dc, rst, busy, delay);
+        // Same drawing and flushing as with an owned buffer
+        let mut owned = new_display();
+        let draw = |target: &mut Gdep073e01<'_, _, _, _, _, _, _>| {
+            target.set_pixel(5, 7, Color::Red);
+            target.fill_span(10, 20, 3, Color::Blue);
+            target.flush().unwrap();
+        };
+        draw(&mut display);
+        draw(&mut owned);
+        assert_eq!(*display.buffer, *owned.buffer);
+        assert_eq!(display.spi.writes, owned.spi.writes);
+        drop(display);
+        assert_eq!(storage[..], *owned.buffer);
+    }
 
-        display.set_pixel(0, 0, Color::Black);
-        assert_eq!(display.buffer[0], 0x01);
+    #[test]
+    fn test_try_new() {
+        let display = Gdep073e01::try_new(
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockDelay::default(),
+        )
+        .unwrap();
+        assert_eq!(*display.buffer, *new_display().buffer);
 
-        display.set_pixel(1, 0, Color::Red);
-        assert_eq!(display.buffer[0], 0x03);
+        // An allocation the heap cannot satisfy is reported, not aborted on;
+        // tests/try_new.rs covers `try_new` itself under a refusing allocator
+        assert!(try_alloc_buffer(usize::MAX).is_err());
+        assert!(try_alloc_buffer(isize::MAX as usize).is_err());
     }
 
     #[test]
-    fn test_clear_buffer() {
-        let spi = MockSpi::default();
-        let cs = MockPin::default();
-        let dc = MockPin::default();
-        let rst = MockPin::default();
-        let busy = MockPin::default();
-        let delay = MockDelay;
+    fn test_init_with_sequence() {
+        let mut display = new_display();
+        display.init().unwrap();
+        let default_writes = display.spi.writes.clone();
 
-        let mut display = Gdep073e01::new(spi, cs, dc, rst, busy, delay);
+        let mut display = new_display();
+        display
+            .init_with_sequence(&[(CMD_POWER_SETTING, &[0x2F]), (CMD_PWS, &[0x22])])
+            .unwrap();
+        assert_eq!(
+            display.spi.writes,
+            [
+                vec![CMD_POWER_SETTING],
+                vec![0x2F],
+                vec![CMD_PWS],
+                vec![0x22],
+                vec![CMD_POWER_ON]
+            ]
+        );
+        // Reset happened as for init()
+        assert_eq!(display.rst.states, [PinState::Low, PinState::High]);
 
-        display.clear_buffer(Color::Orange);
-        assert!(display.buffer.iter().all(|&byte| byte == 0x44));
+        // init() sends the built-in list the same way
+        let mut sequence: Vec<(u8, Vec<u8>)> = Vec::new();
+        init_sequence::<()>(
+            CMDH_DATA,
+            BoosterProfile::Quality,
+            PANEL_SETTING_DATA,
+            CDI_DATA,
+            FrameRate::Hz50,
+            |command, data| {
+                sequence.push((command, data.to_vec()));
+                Ok(())
+            },
+        )
+        .unwrap();
+        let pairs: Vec<(u8, &[u8])> = sequence.iter().map(|(c, d)| (*c, &d[..])).collect();
+        let mut display = new_display();
+        display.init_with_sequence(&pairs).unwrap();
+        assert_eq!(display.spi.writes, default_writes);
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut display = Gdep073e01Builder::new(
+            MockSpi::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockPin::default(),
+            MockDelay::default(),
+        )
+        .rotation(Rotation::Deg270)
+        .busy_timeout_ms(500)
+        .border_color(Color::Red)
+        .build();
+
+        // Nothing reached the hardware
+        assert!(display.spi.writes.is_empty());
+        assert!(display.rst.states.is_empty());
+        assert!(display.delay.delays_ns.is_empty());
+        assert_eq!(display.rotation(), Rotation::Deg270);
+        assert_eq!(display.busy_timeout_ms, 500);
+        assert_eq!(display.size(), Size::new(HEIGHT, WIDTH));
+
+        display.init().unwrap();
+        assert_eq!(command_data(&display.spi.writes, CMD_CDI), [0x7F]);
+    }
+
+    #[test]
+    fn test_border_color() {
+        let mut display = new_display();
+        display.init().unwrap();
+        assert_eq!(command_data(&display.spi.writes, CMD_CDI), [0x3F]);
+
+        for (color, expected) in [
+            (Color::Black, 0x1F),
+            (Color::Red, 0x7F),
+            (Color::Green, 0xDF),
+        ] {
+            display.spi.writes.clear();
+            display.set_border_color(color).unwrap();
+            assert_eq!(display.spi.writes, [vec![CMD_CDI], vec![expected]]);
+        }
+
+        // Kept across init
+        display.spi.writes.clear();
+        display.init().unwrap();
+        assert_eq!(command_data(&display.spi.writes, CMD_CDI), [0xDF]);
+    }
+
+    #[test]
+    fn test_frame_rate() {
+        let mut display = new_display();
+        display.init().unwrap();
+        assert_eq!(command_data(&display.spi.writes, CMD_PLL_CONTROL), [0x08]);
+
+        display.spi.writes.clear();
+        display.set_frame_rate(FrameRate::Hz50).unwrap();
+        assert_eq!(display.spi.writes, [vec![CMD_PLL_CONTROL], vec![0x08]]);
+        assert_eq!(FrameRate::default(), FrameRate::Hz50);
+    }
+
+    #[test]
+    fn test_scan_direction() {
+        let mut display = new_display();
+        display.init().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PANEL_SETTING),
+            [0x5F, 0x69]
+        );
+
+        for (gate_up, source_left, expected) in [
+            (true, false, 0x5F),
+            (false, true, 0x53),
+            (false, false, 0x57),
+            (true, true, 0x5B),
+        ] {
+            display.spi.writes.clear();
+            display.set_scan_direction(gate_up, source_left).unwrap();
+            assert_eq!(
+                display.spi.writes,
+                [vec![CMD_PANEL_SETTING], vec![expected, 0x69]]
+            );
+        }
+
+        // Kept across init
+        display.spi.writes.clear();
+        display.init().unwrap();
+        assert_eq!(
+            command_data(&display.spi.writes, CMD_PANEL_SETTING),
+            [0x5B, 0x69]
+        );
     }
 
     #[test]
@@ -587,7 +6922,7 @@ mod tests {
         let dc = MockPin::default();
         let rst = MockPin::default();
         let busy = MockPin::default();
-        let delay = MockDelay;
+        let delay = MockDelay::default();
 
         let display = Gdep073e01::new(spi, cs, dc, rst, busy, delay);
         assert_eq!(display.size(), Size::new(WIDTH, HEIGHT));
@@ -3,6 +3,11 @@
 
 /// Fixed Spectra 6 palette order used by the panel’s LUT/driver (excluding Orange).
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Spectra6 {
     White,
     Black,
@@ -12,9 +17,23 @@ pub enum Spectra6 {
     Blue,
 }
 
+impl core::fmt::Display for Spectra6 {
+    /// Writes the color's name, e.g. `Red`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(match self {
+            Spectra6::White => "White",
+            Spectra6::Black => "Black",
+            Spectra6::Yellow => "Yellow",
+            Spectra6::Red => "Red",
+            Spectra6::Green => "Green",
+            Spectra6::Blue => "Blue",
+        })
+    }
+}
+
 /// Panel order palette RGB centers in sRGB 8-bit.
 /// Order: White, Black, Yellow, Red, Green, Blue
-pub const PALETTE: [[u8; 3]; 6] = [
+pub const PALETTE: [[u8; 3]; Spectra6::COUNT] = [
     [255, 255, 255], // White
     [0, 0, 0],       // Black
     [255, 255, 0],   // Yellow
@@ -24,6 +43,27 @@ pub const PALETTE: [[u8; 3]; 6] = [
 ];
 
 impl Spectra6 {
+    /// Number of colors.
+    pub const COUNT: usize = 6;
+
+    /// Iterates over every color once, in `PALETTE` order.
+    pub fn iter() -> impl Iterator<Item = Spectra6> {
+        (0..Self::COUNT).map(Self::from_index)
+    }
+
+    /// Spectra6 color at `index` in `PALETTE` order (out of range maps to Blue).
+    #[inline]
+    const fn from_index(index: usize) -> Spectra6 {
+        match index {
+            0 => Spectra6::White,
+            1 => Spectra6::Black,
+            2 => Spectra6::Yellow,
+            3 => Spectra6::Red,
+            4 => Spectra6::Green,
+            _ => Spectra6::Blue,
+        }
+    }
+
     /// Convert this Spectra6 color to the driver's 7-color `Color` variant.
     /// Note: Orange is intentionally not used by Spectra6.
     pub fn to_driver_color(self) -> crate::Color {
@@ -38,10 +78,86 @@ impl Spectra6 {
     }
 }
 
+/// Spectra6 palette plus Orange, the seventh pigment of the panel.
+///
+/// Indices 0-5 match [`Spectra6`] and [`PALETTE`]; Orange comes last.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Spectra7 {
+    /// White pigment
+    White,
+    /// Black pigment
+    Black,
+    /// Yellow pigment
+    Yellow,
+    /// Red pigment
+    Red,
+    /// Green pigment
+    Green,
+    /// Blue pigment
+    Blue,
+    /// Orange pigment
+    Orange,
+}
+
+/// `PALETTE` followed by Orange's sRGB center, taken from the driver colors.
+/// Order: White, Black, Yellow, Red, Green, Blue, Orange
+pub const PALETTE7: [[u8; 3]; Spectra7::COUNT] = {
+    let mut palette = [[0; 3]; Spectra7::COUNT];
+    let mut i = 0;
+    while i < Spectra7::COUNT {
+        palette[i] = Spectra7::from_index(i).to_driver_color().rgb();
+        i += 1;
+    }
+    palette
+};
+
+impl Spectra7 {
+    /// Number of colors.
+    pub const COUNT: usize = 7;
+
+    /// Spectra7 color at `index` in `PALETTE7` order (out of range maps to Orange).
+    #[inline]
+    const fn from_index(index: usize) -> Spectra7 {
+        match index {
+            0 => Spectra7::White,
+            1 => Spectra7::Black,
+            2 => Spectra7::Yellow,
+            3 => Spectra7::Red,
+            4 => Spectra7::Green,
+            5 => Spectra7::Blue,
+            _ => Spectra7::Orange,
+        }
+    }
+
+    /// Convert this Spectra7 color to the driver's `Color` variant.
+    pub const fn to_driver_color(self) -> crate::Color {
+        match self {
+            Spectra7::White => crate::Color::White,
+            Spectra7::Black => crate::Color::Black,
+            Spectra7::Yellow => crate::Color::Yellow,
+            Spectra7::Red => crate::Color::Red,
+            Spectra7::Green => crate::Color::Green,
+            Spectra7::Blue => crate::Color::Blue,
+            Spectra7::Orange => crate::Color::Orange,
+        }
+    }
+}
+
+impl From<Spectra6> for Spectra7 {
+    fn from(color: Spectra6) -> Self {
+        Spectra7::from_index(color as usize)
+    }
+}
+
 /// Cheap perceptual-ish distance between two sRGB triples (0..=255).
 /// Uses a weighted squared distance to approximate luminance sensitivity without floats.
 #[inline]
-fn dist2_weighted(a: [u8; 3], b: [u8; 3]) -> u32 {
+pub(crate) fn dist2_weighted(a: [u8; 3], b: [u8; 3]) -> u32 {
     let dr = a[0] as i32 - b[0] as i32;
     let dg = a[1] as i32 - b[1] as i32;
     let db = a[2] as i32 - b[2] as i32;
@@ -50,6 +166,40 @@ fn dist2_weighted(a: [u8; 3], b: [u8; 3]) -> u32 {
     (3 * dr * dr + 6 * dg * dg + 1 * db * db) as u32
 }
 
+include!(concat!(env!("OUT_DIR"), "/srgb_linear.rs"));
+
+/// `dist2_weighted` for linear-light triples from `SRGB_TO_LINEAR`.
+#[inline]
+fn dist2_weighted_linear(a: [u16; 3], b: [u16; 3]) -> u32 {
+    let d = |i: usize| a[i] as i32 - b[i] as i32;
+    // Same weights as `dist2_weighted`; 10 * 4095^2 fits in u32
+    (3 * d(0) * d(0) + 6 * d(1) * d(1) + d(2) * d(2)) as u32
+}
+
+/// RGB -> closest Spectra6 color, compared in linear light (no dither).
+///
+/// Decodes the sRGB gamma through a 256-entry table before applying the
+/// weighted metric of [`map_rgb_to_spectra6_nearest`]. Distances then
+/// follow the amount of light rather than the encoded value, so dark
+/// grays and navy blues go to Black instead of being pulled towards
+/// White or Blue; sRGB 128 is only 22% light and maps to Black here but
+/// White on the gamma-encoded path.
+#[inline]
+pub fn map_rgb_to_spectra6_linear(rgb: [u8; 3]) -> Spectra6 {
+    let linear = |c: [u8; 3]| c.map(|v| SRGB_TO_LINEAR[v as usize]);
+    let p = linear(rgb);
+    let mut best = 0usize;
+    let mut best_d = u32::MAX;
+    for (i, &q) in PALETTE.iter().enumerate() {
+        let d = dist2_weighted_linear(p, linear(q));
+        if d < best_d {
+            best_d = d;
+            best = i;
+        }
+    }
+    Spectra6::from_index(best)
+}
+
 /// RGB -> closest Spectra6 color (no dither).
 #[inline]
 pub fn map_rgb_to_spectra6_nearest(rgb: [u8; 3]) -> Spectra6 {
@@ -63,14 +213,211 @@ pub fn map_rgb_to_spectra6_nearest(rgb: [u8; 3]) -> Spectra6 {
             best = i;
         }
     }
-    match best {
-        0 => Spectra6::White,
-        1 => Spectra6::Black,
-        2 => Spectra6::Yellow,
-        3 => Spectra6::Red,
-        4 => Spectra6::Green,
-        _ => Spectra6::Blue,
+    Spectra6::from_index(best)
+}
+
+/// RGB -> closest Spectra7 color, Orange included (no dither).
+///
+/// Same weighted metric as [`map_rgb_to_spectra6_nearest`], which keeps
+/// matching the six Spectra 6 colors only; pick this one to let oranges
+/// and sunsets land on the Orange pigment instead of Red or Yellow.
+#[inline]
+pub fn map_rgb_to_spectra7_nearest(rgb: [u8; 3]) -> Spectra7 {
+    let mut best = 0usize;
+    let mut best_d = u32::MAX;
+    for (i, p) in PALETTE7.iter().enumerate() {
+        let d = dist2_weighted(rgb, *p);
+        if d < best_d {
+            best_d = d;
+            best = i;
+        }
+    }
+    Spectra7::from_index(best)
+}
+
+/// Hue window, in degrees, in which warm colors lean towards Orange (hue 30°).
+const ORANGE_HUE: core::ops::RangeInclusive<i32> = 18..=45;
+/// Minimum channel spread (max - min) for a color to have a usable hue.
+const ORANGE_MIN_CHROMA: i32 = 64;
+
+/// True if `rgb` is saturated enough and its hue lies in `ORANGE_HUE`.
+#[inline]
+fn is_orange_hue(rgb: [u8; 3]) -> bool {
+    let [r, g, b] = rgb.map(i32::from);
+    let chroma = r - g.min(b);
+    if r < g || chroma < ORANGE_MIN_CHROMA {
+        return false;
+    }
+    ORANGE_HUE.contains(&(60 * (g - b) / chroma))
+}
+
+/// RGB -> closest Spectra7 color, with a hue tiebreak for Orange.
+///
+/// Matches like [`map_rgb_to_spectra7_nearest`], but a saturated warm color
+/// whose hue lies within about 18-45° takes Orange when the metric picked
+/// Red or Yellow. Orange sits between the two, so darker or muted oranges
+/// otherwise fall to Red. Grays and dark browns keep their Black/White
+/// match.
+#[inline]
+pub fn map_rgb_to_spectra7_hue_aware(rgb: [u8; 3]) -> Spectra7 {
+    match map_rgb_to_spectra7_nearest(rgb) {
+        Spectra7::Red | Spectra7::Yellow if is_orange_hue(rgb) => Spectra7::Orange,
+        other => other,
+    }
+}
+
+/// 32x32x32 RGB -> Spectra6 index table generated by `build.rs`.
+/// Indexed by the top 5 bits of each channel.
+static SPECTRA6_LUT: &[u8; 32 * 32 * 32] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/spectra6_lut.bin"));
+
+/// RGB -> perceptually closest Spectra6 color via a precomputed table (no dither).
+///
+/// The table is built at compile time from OkLab distances, with chroma
+/// weighted up so near-grays stay black/white, and quantizes each channel to 5
+/// bits. It costs 32 KiB of flash but no floats at runtime, and handles
+/// purples, salmon and deep blues better than the weighted RGB metric.
+#[inline]
+pub fn map_rgb_to_spectra6_lut(rgb: [u8; 3]) -> Spectra6 {
+    let index =
+        ((rgb[0] as usize >> 3) << 10) | ((rgb[1] as usize >> 3) << 5) | (rgb[2] as usize >> 3);
+    Spectra6::from_index(SPECTRA6_LUT[index] as usize)
+}
+
+/// 32x32x32 RGB -> native color code table generated by `build.rs`.
+/// Indexed like `SPECTRA6_LUT`.
+#[cfg(feature = "color-lut")]
+static COLOR7_LUT: &[u8; 32 * 32 * 32] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/color7_lut.bin"));
+
+/// RGB -> closest of all seven driver colors, Orange included, via a table.
+///
+/// The table holds the weighted RGB nearest match (the metric of
+/// `palette_error`) at the center of each 5-bit-per-channel cell. It costs
+/// 32 KiB of flash and replaces seven distance computations with one load;
+/// colors within 4 levels of a boundary may land on its other side.
+#[cfg(feature = "color-lut")]
+#[inline]
+pub fn lut_nearest_color(rgb: [u8; 3]) -> crate::Color {
+    let index =
+        ((rgb[0] as usize >> 3) << 10) | ((rgb[1] as usize >> 3) << 5) | (rgb[2] as usize >> 3);
+    crate::Color::from_nibble(COLOR7_LUT[index]).unwrap_or(crate::Color::White)
+}
+
+#[cfg(feature = "oklab")]
+include!(concat!(env!("OUT_DIR"), "/oklab.rs"));
+
+/// Extra weight on OkLab chroma, as used for `SPECTRA6_LUT` by `build.rs`.
+#[cfg(feature = "oklab")]
+const OKLAB_CHROMA_WEIGHT: f32 = 3.0;
+
+/// Cube root of a non-negative `x`, without libm.
+///
+/// An exponent-halving bit trick for the first guess, then three Newton
+/// steps; within 1e-6 relative error over `0.0..=1.0`.
+#[cfg(feature = "oklab")]
+#[inline]
+fn cbrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut y = f32::from_bits(x.to_bits() / 3 + 709_921_077);
+    for _ in 0..3 {
+        y = (2.0 * y + x / (y * y)) / 3.0;
+    }
+    y
+}
+
+/// sRGB triple -> OkLab (L, a, b).
+#[cfg(feature = "oklab")]
+#[inline]
+fn oklab(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| SRGB_TO_LINEAR_F32[c as usize]);
+    let l = cbrt(0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b);
+    let m = cbrt(0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b);
+    let s = cbrt(0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b);
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// RGB -> perceptually closest Spectra6 color in OkLab, computed exactly (no dither).
+///
+/// Decodes sRGB through a 256-entry table, converts to OkLab with `f32`
+/// math (no libm) and picks the nearest palette color, with chroma
+/// weighted up like `map_rgb_to_spectra6_lut` so near-grays stay
+/// black/white. It agrees with that table except near boundaries, where
+/// the table's 5-bit quantization misplaces mid-tones such as skin and
+/// sky gradients. Costs about six cube roots per pixel; on targets
+/// without an FPU prefer the table.
+#[cfg(feature = "oklab")]
+pub fn map_rgb_to_spectra6_oklab(rgb: [u8; 3]) -> Spectra6 {
+    let p = oklab(rgb);
+    let dist = |q: &[f32; 3]| {
+        let (dl, da, db) = (p[0] - q[0], p[1] - q[1], p[2] - q[2]);
+        dl * dl + OKLAB_CHROMA_WEIGHT * (da * da + db * db)
+    };
+    let mut best = 0;
+    for (i, q) in PALETTE_OKLAB.iter().enumerate() {
+        if dist(q) < dist(&PALETTE_OKLAB[best]) {
+            best = i;
+        }
     }
+    Spectra6::from_index(best)
+}
+
+/// RGB -> closest Spectra6 color among `allowed` (no dither).
+/// Falls back to the full palette if `allowed` is empty.
+#[inline]
+pub fn map_rgb_to_spectra6_nearest_masked(rgb: [u8; 3], allowed: &[Spectra6]) -> Spectra6 {
+    let mut best = None;
+    let mut best_d = u32::MAX;
+    for &c in allowed {
+        let d = dist2_weighted(rgb, PALETTE[c as usize]);
+        if d < best_d {
+            best_d = d;
+            best = Some(c);
+        }
+    }
+    best.unwrap_or_else(|| map_rgb_to_spectra6_nearest(rgb))
+}
+
+/// Total weighted RGB error of a quantized image, for comparing dither strategies.
+///
+/// Sums the same weighted squared distance used for matching between each
+/// source pixel and the nominal RGB of the color chosen for it. Lower is
+/// closer; only pixel pairs present in both slices are compared.
+pub fn palette_error(src: &[[u8; 3]], result: &[crate::Color]) -> u64 {
+    src.iter()
+        .zip(result)
+        .map(|(&rgb, color)| dist2_weighted(rgb, color.rgb()) as u64)
+        .sum()
+}
+
+/// 8x8 blue-noise ranks 0..=63 (void-and-cluster, toroidal).
+const BLUE_NOISE8X8: [[u8; 8]; 8] = [
+    [15, 27, 50, 16, 47, 26, 8, 38],
+    [57, 7, 35, 30, 58, 13, 51, 22],
+    [44, 54, 11, 43, 4, 39, 32, 1],
+    [28, 18, 24, 63, 21, 55, 17, 60],
+    [6, 40, 49, 0, 34, 9, 48, 36],
+    [59, 31, 14, 53, 29, 45, 25, 12],
+    [20, 42, 10, 37, 56, 19, 2, 52],
+    [46, 3, 61, 23, 5, 41, 62, 33],
+];
+
+/// RGB -> Spectra6 after a small position-dependent blue-noise nudge.
+///
+/// The input is offset by -8..=7 per channel from a tiled 8x8 blue-noise
+/// mask before nearest matching. Flat areas near a palette boundary break
+/// up into an irregular mix instead of banding, without the visible cross
+/// hatch of Bayer or the state of error diffusion.
+#[inline]
+pub fn map_rgb_to_spectra6_noisy(rgb: [u8; 3], x: u32, y: u32) -> Spectra6 {
+    let bias = (BLUE_NOISE8X8[(y & 7) as usize][(x & 7) as usize] / 4) as i16 - 8;
+    map_rgb_to_spectra6_nearest(add_bias(rgb, [bias; 3]))
 }
 
 /// Utility: clamp i32 to 0..=255 and return u8.
@@ -95,10 +442,161 @@ pub fn add_bias(rgb: [u8; 3], bias: [i16; 3]) -> [u8; 3] {
     ]
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
+    #[test]
+    fn spectra6_iter() {
+        let colors: alloc::vec::Vec<Spectra6> = Spectra6::iter().collect();
+        assert_eq!(colors.len(), Spectra6::COUNT);
+        for (i, a) in colors.iter().enumerate() {
+            assert!(colors[i + 1..].iter().all(|b| b != a));
+        }
+        assert_eq!(colors[4], Spectra6::Green);
+    }
+
+    #[test]
+    fn palette_error_bounds() {
+        use crate::Color;
+
+        let src = [[255, 0, 0], [255, 128, 0], [0, 0, 0], [255, 255, 255]];
+        let exact = [Color::Red, Color::Orange, Color::Black, Color::White];
+        assert_eq!(palette_error(&src, &exact), 0);
+
+        let white = [[255, 255, 255]; 4];
+        let worst = palette_error(&white, &[Color::Black; 4]);
+        assert_eq!(worst, 4 * 10 * 255 * 255);
+        assert!(palette_error(&white, &[Color::Yellow; 4]) < worst);
+    }
+
+    #[test]
+    fn display_names() {
+        use alloc::format;
+
+        assert_eq!(format!("{}", Spectra6::Red), "Red");
+        assert_eq!(format!("{}", Spectra6::White), "White");
+    }
+
+    #[test]
+    fn noisy_breaks_up_flats() {
+        // Halfway between White and Yellow
+        let flat = [255, 255, 128];
+        let colors: alloc::vec::Vec<Spectra6> = (0..64)
+            .map(|i| map_rgb_to_spectra6_noisy(flat, i % 8, i / 8))
+            .collect();
+        // Half the mask ranks push blue below the boundary
+        let yellow = colors.iter().filter(|&&c| c == Spectra6::Yellow).count();
+        assert_eq!(yellow, 32);
+        assert!(colors
+            .iter()
+            .all(|&c| c == Spectra6::Yellow || c == Spectra6::White));
+        // Horizontally adjacent pixels differ often
+        let changes = colors
+            .chunks(8)
+            .flat_map(|row| row.windows(2))
+            .filter(|pair| pair[0] != pair[1])
+            .count();
+        assert!(changes >= 28, "{changes} changes");
+        // Far from a boundary the nudge changes nothing
+        assert_eq!(map_rgb_to_spectra6_noisy([250, 5, 5], 3, 3), Spectra6::Red);
+    }
+
+    #[test]
+    fn spectra7_nearest_selects_orange() {
+        let orange = [250, 130, 10];
+        assert_eq!(map_rgb_to_spectra7_nearest(orange), Spectra7::Orange);
+        assert_eq!(
+            map_rgb_to_spectra7_nearest(orange).to_driver_color(),
+            crate::Color::Orange
+        );
+        // The 6-color path is unchanged
+        assert_ne!(
+            map_rgb_to_spectra6_nearest(orange).to_driver_color(),
+            crate::Color::Orange
+        );
+
+        // Away from orange both agree
+        for rgb in [[250, 250, 250], [10, 10, 10], [240, 20, 10], [20, 30, 220]] {
+            let six = map_rgb_to_spectra6_nearest(rgb);
+            assert_eq!(map_rgb_to_spectra7_nearest(rgb), Spectra7::from(six));
+        }
+        for (i, &rgb) in PALETTE7.iter().enumerate() {
+            assert_eq!(map_rgb_to_spectra7_nearest(rgb) as usize, i);
+            assert_eq!(Spectra7::from_index(i).to_driver_color().rgb(), rgb);
+        }
+    }
+
+    #[cfg(feature = "oklab")]
+    #[test]
+    fn oklab_matches_lut_at_cell_centers() {
+        for x in [0.0, 1e-4, 0.008, 0.2, 0.5, 1.0] {
+            let y = cbrt(x);
+            assert!(
+                (y * y * y - x).abs() <= 1e-6 * x.max(1e-3),
+                "cbrt({x}) = {y}"
+            );
+        }
+        let white = oklab([255, 255, 255]);
+        assert!((white[0] - 1.0).abs() < 1e-4 && white[1].abs() < 1e-4);
+
+        // The table samples the same metric at the center of each 8-level cell
+        let center = |q: u8| (q << 3) + 4;
+        let mut mismatches = 0;
+        for r in 0..32 {
+            for g in 0..32 {
+                for b in 0..32 {
+                    let rgb = [center(r), center(g), center(b)];
+                    if map_rgb_to_spectra6_oklab(rgb) != map_rgb_to_spectra6_lut(rgb) {
+                        mismatches += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(mismatches, 0);
+        assert_eq!(map_rgb_to_spectra6_oklab([30, 60, 200]), Spectra6::Blue);
+    }
+
+    #[test]
+    fn linear_matching_darkens_mid_grays() {
+        assert_eq!(SRGB_TO_LINEAR[0], 0);
+        assert_eq!(SRGB_TO_LINEAR[255], 4095);
+        assert_eq!(SRGB_TO_LINEAR[128], 884);
+
+        // Mid-grays: the gamma-encoded path rounds up to White
+        for v in [128, 150, 170] {
+            let gray = [v; 3];
+            assert_eq!(map_rgb_to_spectra6_nearest(gray), Spectra6::White);
+            assert_eq!(map_rgb_to_spectra6_linear(gray), Spectra6::Black);
+        }
+        assert_eq!(map_rgb_to_spectra6_linear([200; 3]), Spectra6::White);
+        assert_eq!(map_rgb_to_spectra6_linear([100; 3]), Spectra6::Black);
+        for &rgb in PALETTE.iter() {
+            assert_eq!(
+                map_rgb_to_spectra6_linear(rgb),
+                map_rgb_to_spectra6_nearest(rgb)
+            );
+        }
+    }
+
+    #[test]
+    fn hue_aware_prefers_orange() {
+        assert_eq!(
+            map_rgb_to_spectra7_hue_aware([255, 140, 20]),
+            Spectra7::Orange
+        );
+        assert_eq!(map_rgb_to_spectra7_hue_aware([255, 40, 20]), Spectra7::Red);
+        // Dark orange: the weighted metric alone picks Red
+        let dark = [180, 60, 0];
+        assert_eq!(map_rgb_to_spectra7_nearest(dark), Spectra7::Red);
+        assert_eq!(map_rgb_to_spectra7_hue_aware(dark), Spectra7::Orange);
+        assert_eq!(
+            map_rgb_to_spectra7_hue_aware([250, 240, 10]),
+            Spectra7::Yellow
+        );
+        assert_eq!(map_rgb_to_spectra7_hue_aware([60, 40, 30]), Spectra7::Black);
+    }
+
     #[test]
     fn nearest_basic() {
         assert_eq!(
@@ -115,6 +613,108 @@ mod tests {
         assert_eq!(map_rgb_to_spectra6_nearest([10, 10, 250]), Spectra6::Blue);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spectra6_serde_round_trip() {
+        for c in [
+            Spectra6::White,
+            Spectra6::Black,
+            Spectra6::Yellow,
+            Spectra6::Red,
+            Spectra6::Green,
+            Spectra6::Blue,
+        ] {
+            let json = serde_json::to_string(&c).unwrap();
+            assert_eq!(serde_json::from_str::<Spectra6>(&json).unwrap(), c);
+        }
+        assert_eq!(
+            serde_json::to_string(&Spectra6::Green).unwrap(),
+            "\"green\""
+        );
+    }
+
+    #[test]
+    fn nearest_masked() {
+        let greenish = [40, 200, 60];
+        assert_eq!(map_rgb_to_spectra6_nearest(greenish), Spectra6::Green);
+        let no_green = [
+            Spectra6::White,
+            Spectra6::Black,
+            Spectra6::Yellow,
+            Spectra6::Red,
+            Spectra6::Blue,
+        ];
+        assert_eq!(
+            map_rgb_to_spectra6_nearest_masked(greenish, &no_green),
+            Spectra6::Yellow
+        );
+        let bwr = [Spectra6::Black, Spectra6::White, Spectra6::Red];
+        assert_eq!(
+            map_rgb_to_spectra6_nearest_masked(greenish, &bwr),
+            Spectra6::White
+        );
+        assert_eq!(
+            map_rgb_to_spectra6_nearest_masked(greenish, &[]),
+            Spectra6::Green
+        );
+    }
+
+    #[test]
+    fn lut_vs_weighted() {
+        // Primaries and neutrals agree
+        for rgb in [[250, 250, 250], [5, 5, 5], [128, 128, 128], [10, 10, 250]] {
+            assert_eq!(
+                map_rgb_to_spectra6_lut(rgb),
+                map_rgb_to_spectra6_nearest(rgb)
+            );
+        }
+        // Salmon: weighted RGB picks Yellow
+        assert_eq!(
+            map_rgb_to_spectra6_nearest([224, 128, 112]),
+            Spectra6::Yellow
+        );
+        assert_eq!(map_rgb_to_spectra6_lut([224, 128, 112]), Spectra6::Red);
+        // Purple: weighted RGB picks Red
+        assert_eq!(map_rgb_to_spectra6_nearest([176, 32, 224]), Spectra6::Red);
+        assert_eq!(map_rgb_to_spectra6_lut([176, 32, 224]), Spectra6::Blue);
+        // Ocean blue: weighted RGB picks Green
+        assert_eq!(map_rgb_to_spectra6_nearest([0, 144, 192]), Spectra6::Green);
+        assert_eq!(map_rgb_to_spectra6_lut([0, 144, 192]), Spectra6::Blue);
+    }
+
+    #[cfg(feature = "color-lut")]
+    #[test]
+    fn color_lut_matches_exact() {
+        use crate::Color;
+
+        let exact = |rgb: [u8; 3]| {
+            Color::ALL
+                .into_iter()
+                .min_by_key(|c| dist2_weighted(rgb, c.rgb()))
+                .unwrap()
+        };
+        // Exact at cell centers
+        for q in (0..32 * 32 * 32).step_by(37) {
+            let center = |shift: u32| (((q >> shift) & 31) << 3) as u8 + 4;
+            let rgb = [center(10), center(5), center(0)];
+            assert_eq!(lut_nearest_color(rgb), exact(rgb), "{rgb:?}");
+        }
+        // Elsewhere the error grows by at most the 4-level offset to the center:
+        // 2 * 4 * 255 * (3 + 6 + 1)
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(51) {
+                    let rgb = [r as u8, g as u8, b as u8];
+                    let lut = dist2_weighted(rgb, lut_nearest_color(rgb).rgb());
+                    assert!(lut <= dist2_weighted(rgb, exact(rgb).rgb()) + 20400);
+                }
+            }
+        }
+        assert_eq!(lut_nearest_color([255, 128, 0]), Color::Orange);
+        assert_eq!(lut_nearest_color([250, 10, 10]), Color::Red);
+        assert_eq!(lut_nearest_color([5, 5, 5]), Color::Black);
+    }
+
     #[test]
     fn spectra6_to_driver_color_nibbles() {
         // Verify that Spectra6 maps to the native nibble codes used by the panel,
@@ -0,0 +1,162 @@
+//! Portrait (480x800) frame buffer, turned to the panel's layout at flush.
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+    spi::SpiDevice,
+};
+
+use crate::{Color, Error, Gdep073e01, HEIGHT, WIDTH};
+
+/// Logical width in portrait orientation
+pub const PORTRAIT_WIDTH: u32 = HEIGHT;
+/// Logical height in portrait orientation
+pub const PORTRAIT_HEIGHT: u32 = WIDTH;
+
+const ROW_BYTES: usize = PORTRAIT_WIDTH as usize / 2;
+
+/// GDEP073E01 driver with a 480x800 portrait frame buffer.
+///
+/// The buffer is stored in portrait order, so drawing costs the same as in
+/// landscape; the frame is turned into the panel's 800x480 layout once per
+/// `flush()`, row by row, with no second buffer. Logical (0, 0) is the
+/// physical bottom-left corner: mount the panel turned 90° clockwise.
+///
+/// Only portrait-aware operations are exposed. Buffer methods of the
+/// wrapped driver assume the landscape layout; reach it through
+/// [`display_mut`](Self::display_mut) for configuration only.
+pub struct PortraitGdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY> {
+    inner: Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY>,
+}
+
+impl<'a, SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>
+    PortraitGdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    /// Creates a portrait driver; see [`Gdep073e01::new`] for the arguments.
+    #[cfg(feature = "alloc")]
+    pub fn new(spi: SPI, cs: CS, dc: DC, rst: RST, busy: BUSY, delay: DELAY) -> Self {
+        Self {
+            inner: Gdep073e01::new(spi, cs, dc, rst, busy, delay),
+        }
+    }
+
+    /// Wraps a configured driver, clearing its buffer to white.
+    pub fn from_display(mut display: Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY>) -> Self {
+        display.clear_buffer(Color::White);
+        Self { inner: display }
+    }
+
+    /// Returns the wrapped driver. Its buffer still holds the portrait layout.
+    pub fn into_inner(self) -> Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY> {
+        self.inner
+    }
+
+    /// Mutable access to the wrapped driver, e.g. for timing or power settings.
+    pub fn display_mut(&mut self) -> &mut Gdep073e01<'a, SPI, CS, DC, RST, BUSY, DELAY> {
+        &mut self.inner
+    }
+
+    /// Initializes the display.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn init(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.inner.init()
+    }
+
+    /// Sets a pixel in portrait coordinates; out-of-range pixels are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - X coordinate (0 to PORTRAIT_WIDTH-1)
+    /// * `y` - Y coordinate (0 to PORTRAIT_HEIGHT-1)
+    /// * `color` - Pixel color
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: Color) {
+        if x >= PORTRAIT_WIDTH || y >= PORTRAIT_HEIGHT {
+            return;
+        }
+        let index = y as usize * ROW_BYTES + x as usize / 2;
+        let shift = if x & 1 == 0 { 4 } else { 0 };
+        let byte = &mut self.inner.buffer[index];
+        *byte = (*byte & !(0x0F << shift)) | ((color as u8) << shift);
+    }
+
+    /// Clears the buffer with the specified color.
+    pub fn clear_buffer(&mut self, color: Color) {
+        // A uniform fill looks the same in either layout
+        self.inner.clear_buffer(color);
+    }
+
+    /// Sends the buffer in the panel's layout and refreshes.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures or timeout.
+    pub fn flush(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.inner.flush_portrait()
+    }
+
+    /// Puts the display into deep sleep mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for communication failures.
+    pub fn sleep(&mut self) -> Result<(), Error<SpiE, PinE, BusyE>> {
+        self.inner.sleep()
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE> DrawTarget
+    for PortraitGdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    type Color = Color;
+    type Error = Error<SpiE, PinE, BusyE>;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 {
+                self.set_pixel(point.x as u32, point.y as u32, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        for point in area.intersection(&self.bounding_box()).points() {
+            self.set_pixel(point.x as u32, point.y as u32, color);
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.clear_buffer(color);
+        Ok(())
+    }
+}
+
+impl<SPI, CS, DC, RST, BUSY, DELAY> OriginDimensions
+    for PortraitGdep073e01<'_, SPI, CS, DC, RST, BUSY, DELAY>
+{
+    fn size(&self) -> Size {
+        Size::new(PORTRAIT_WIDTH, PORTRAIT_HEIGHT)
+    }
+}
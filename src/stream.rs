@@ -0,0 +1,209 @@
+//! Framebuffer-free streaming for the most constrained setups.
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{InputPin, OutputPin},
+    spi::SpiDevice,
+};
+
+use crate::palette::{add_bias, map_rgb_to_spectra6_nearest};
+use crate::{
+    delay_step, init_sequence, pack_nibble, poll_until_busy, poll_until_idle, BoardPreset,
+    BoosterProfile, BusyWaitError, Error, FrameRate, BUSY_TIMEOUT_MS, CDI_DATA, CMDH_DATA,
+    CMD_DATA_START_TRANSMISSION, CMD_DEEP_SLEEP, CMD_DISPLAY_REFRESH, CMD_POWER_OFF, CMD_POWER_ON,
+    HEIGHT, PANEL_SETTING_DATA, WIDTH,
+};
+
+const ROW_BYTES: usize = WIDTH as usize / 2;
+
+/// Board timing for [`present_rgb_ordered_with`].
+///
+/// Mirrors the driver's `with_defaults_for`, `set_busy_timeout_ms` and
+/// `set_busy_ack_timeout` settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamConfig {
+    /// Reset timing and BUSY polarity.
+    pub preset: BoardPreset,
+    /// How long BUSY waits may take before failing with `Error::Timeout`; 0 waits forever.
+    pub busy_timeout_ms: u32,
+    /// Requires BUSY to assert within this many milliseconds of the refresh command.
+    pub busy_ack_timeout_ms: Option<u32>,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            preset: BoardPreset::Generic,
+            busy_timeout_ms: BUSY_TIMEOUT_MS,
+            busy_ack_timeout_ms: None,
+        }
+    }
+}
+
+/// Shows an `Rgb888` image with ordered dithering, using no frame buffer and no heap.
+///
+/// Resets and initializes the panel, streams the image one packed row at a
+/// time from a 400-byte stack buffer, refreshes and puts the panel into deep
+/// sleep. The image is placed top-left; the rest of the panel is white.
+/// Each channel is nudged by `matrix[y % 4][x % 4] - 8` before nearest
+/// palette matching, so `matrix` holds thresholds 0..=15 (for example a
+/// Bayer matrix). Uses the default [`StreamConfig`] and booster profile;
+/// see [`present_rgb_ordered_with`] for other boards.
+///
+/// # Arguments
+///
+/// * `spi`, `cs`, `dc`, `rst`, `busy`, `delay` - Panel interface, as for
+///   [`Gdep073e01::new`](crate::Gdep073e01::new)
+/// * `width`, `height` - Image size in pixels
+/// * `src` - Pixels in row-major order
+/// * `matrix` - 4x4 ordered dither thresholds
+///
+/// # Errors
+///
+/// Returns `Error::InvalidLength`, before touching the panel, if `src` does
+/// not hold `width * height` pixels or the image does not fit the display.
+/// Otherwise returns errors for communication failures or timeout.
+#[allow(clippy::too_many_arguments)]
+pub fn present_rgb_ordered<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>(
+    spi: &mut SPI,
+    cs: &mut CS,
+    dc: &mut DC,
+    rst: &mut RST,
+    busy: &mut BUSY,
+    delay: &mut DELAY,
+    width: u32,
+    height: u32,
+    src: &[[u8; 3]],
+    matrix: &[[u8; 4]; 4],
+) -> Result<(), Error<SpiE, PinE, BusyE>>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    present_rgb_ordered_with(
+        spi,
+        cs,
+        dc,
+        rst,
+        busy,
+        delay,
+        width,
+        height,
+        src,
+        matrix,
+        &StreamConfig::default(),
+    )
+}
+
+/// Shows an image like [`present_rgb_ordered`], with the given board timing.
+///
+/// # Errors
+///
+/// As for [`present_rgb_ordered`]; additionally returns
+/// `Error::BusyNeverAsserted` if `config.busy_ack_timeout_ms` is set and BUSY
+/// does not assert after the refresh command.
+#[allow(clippy::too_many_arguments)]
+pub fn present_rgb_ordered_with<SPI, CS, DC, RST, BUSY, DELAY, SpiE, PinE, BusyE>(
+    spi: &mut SPI,
+    cs: &mut CS,
+    dc: &mut DC,
+    rst: &mut RST,
+    busy: &mut BUSY,
+    delay: &mut DELAY,
+    width: u32,
+    height: u32,
+    src: &[[u8; 3]],
+    matrix: &[[u8; 4]; 4],
+    config: &StreamConfig,
+) -> Result<(), Error<SpiE, PinE, BusyE>>
+where
+    SPI: SpiDevice<u8, Error = SpiE>,
+    CS: OutputPin<Error = PinE>,
+    DC: OutputPin<Error = PinE>,
+    RST: OutputPin<Error = PinE>,
+    BUSY: InputPin<Error = BusyE>,
+    DELAY: DelayNs,
+{
+    if width > WIDTH || height > HEIGHT || src.len() != width as usize * height as usize {
+        return Err(Error::InvalidLength);
+    }
+
+    let preset = config.preset;
+    rst.set_low().map_err(Error::Pin)?;
+    delay.delay_ms(preset.reset_delay_ms());
+    rst.set_high().map_err(Error::Pin)?;
+    delay.delay_ms(preset.reset_delay_ms());
+
+    let mut send = |is_data: bool, bytes: &[u8]| -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if is_data {
+            dc.set_high().map_err(Error::Pin)?;
+        } else {
+            dc.set_low().map_err(Error::Pin)?;
+        }
+        cs.set_low().map_err(Error::Pin)?;
+        let result = spi.write(bytes).map_err(Error::Spi);
+        cs.set_high().map_err(Error::Pin)?;
+        result
+    };
+    let active_high = preset.busy_active_high();
+    let mut wait = |ack: bool| -> Result<(), Error<SpiE, PinE, BusyE>> {
+        if let (true, Some(timeout_ms)) = (ack, config.busy_ack_timeout_ms) {
+            poll_until_busy(busy, delay, active_high, timeout_ms)?;
+        }
+        poll_until_idle(
+            busy,
+            active_high,
+            config.busy_timeout_ms,
+            &mut 0,
+            |max_ms| Ok(delay_step(delay, max_ms)),
+        )
+        .map(drop)
+        .map_err(|e| match e {
+            BusyWaitError::Pin(e) => Error::Busy(e),
+            BusyWaitError::Timeout(_) | BusyWaitError::Aborted => Error::Timeout,
+        })
+    };
+
+    init_sequence(
+        CMDH_DATA,
+        BoosterProfile::default(),
+        PANEL_SETTING_DATA,
+        CDI_DATA,
+        FrameRate::default(),
+        |command, data| {
+            send(false, &[command])?;
+            send(true, data)
+        },
+    )?;
+    send(false, &[CMD_POWER_ON])?;
+    wait(false)?;
+
+    send(false, &[CMD_DATA_START_TRANSMISSION])?;
+    let mut row = [0u8; ROW_BYTES];
+    for y in 0..HEIGHT {
+        row.fill(0x11);
+        if y < height {
+            let pixels = &src[(y * width) as usize..][..width as usize];
+            for (x, &rgb) in (0u32..).zip(pixels) {
+                let bias = matrix[(y & 3) as usize][(x & 3) as usize] as i16 - 8;
+                let color = map_rgb_to_spectra6_nearest(add_bias(rgb, [bias; 3])).to_driver_color();
+                let byte = &mut row[(x / 2) as usize];
+                *byte = pack_nibble(*byte, x, color);
+            }
+        }
+        send(true, &row)?;
+    }
+
+    send(false, &[CMD_DISPLAY_REFRESH])?;
+    send(true, &[0x00])?;
+    wait(true)?;
+    send(false, &[CMD_POWER_OFF])?;
+    send(true, &[0x00])?;
+    wait(false)?;
+    send(false, &[CMD_DEEP_SLEEP])?;
+    send(true, &[0xA5])
+}
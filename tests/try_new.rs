@@ -0,0 +1,96 @@
+//! `try_new` under an allocator that refuses frame-buffer-sized requests.
+//!
+//! Lives in its own test binary because the `#[global_allocator]` applies to
+//! the whole executable.
+#![cfg(feature = "alloc")]
+
+use core::convert::Infallible;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{ErrorType as PinErrorType, InputPin, OutputPin};
+use embedded_hal::spi::{ErrorType as SpiErrorType, Operation, SpiDevice};
+use gdep073e01::{Gdep073e01, HEIGHT, WIDTH};
+
+const FRAME_BYTES: usize = (WIDTH * HEIGHT / 2) as usize;
+
+/// Fails every allocation of at least a frame buffer while `REFUSE_LARGE` is set.
+struct LimitedAlloc;
+
+static REFUSE_LARGE: AtomicBool = AtomicBool::new(false);
+
+unsafe impl GlobalAlloc for LimitedAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() >= FRAME_BYTES && REFUSE_LARGE.load(Ordering::SeqCst) {
+            return core::ptr::null_mut();
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: LimitedAlloc = LimitedAlloc;
+
+struct MockSpi;
+struct MockPin;
+struct MockDelay;
+
+impl SpiErrorType for MockSpi {
+    type Error = Infallible;
+}
+
+impl SpiDevice<u8> for MockSpi {
+    fn transaction(&mut self, _: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl PinErrorType for MockPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for MockPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl InputPin for MockPin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, _: u32) {}
+}
+
+fn try_new() -> Option<Gdep073e01<'static, MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay>>
+{
+    Gdep073e01::try_new(MockSpi, MockPin, MockPin, MockPin, MockPin, MockDelay).ok()
+}
+
+#[test]
+fn test_try_new_reports_allocation_failure() {
+    assert!(try_new().is_some());
+
+    REFUSE_LARGE.store(true, Ordering::SeqCst);
+    let refused = try_new();
+    REFUSE_LARGE.store(false, Ordering::SeqCst);
+    assert!(refused.is_none());
+
+    assert!(try_new().is_some());
+}